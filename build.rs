@@ -0,0 +1,35 @@
+//! With the `embed` feature, list the files of `rsc` for `input` to include
+//! in the binary.
+use std::path::{Path, PathBuf};
+
+fn visit(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            visit(&path, files);
+        } else if path.extension().is_some_and(|extension| ["txt", "enc", "toml"].contains(&extension.to_str().unwrap())) {
+            files.push(path);
+        }
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let mut files = Vec::new();
+    if std::env::var_os("CARGO_FEATURE_EMBED").is_some() {
+        println!("cargo:rerun-if-changed=rsc");
+        visit(Path::new("rsc"), &mut files);
+        files.sort();
+    }
+
+    let root = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap());
+    let entries = files
+        .iter()
+        .map(|path| {
+            let name = path.to_str().unwrap().replace('\\', "/");
+            format!("    ({:?}, include_str!({:?})),\n", name, root.join(path))
+        })
+        .collect::<String>();
+    let out = PathBuf::from(std::env::var_os("OUT_DIR").unwrap()).join("embedded.rs");
+    std::fs::write(out, format!("pub static FILES: &[(&str, &str)] = &[\n{}];\n", entries)).unwrap();
+}
@@ -10,4 +10,4 @@ crate::run!();
 
 crate::test_example_aoc!(0000, 0000);
 
-crate::test_aoc!(0000, 0000);
+crate::test_aoc!();
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::input;
+use crate::solution::Part;
+
+/// The accepted answers of a day. Missing parts are not known yet.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Expected {
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+}
+
+/// How an answer compares to the recorded one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Check {
+    Correct,
+    Wrong,
+    Unknown,
+    /// The part failed to give an answer.
+    Error,
+}
+
+impl Check {
+    pub fn new(expected: Option<&str>, answer: &str) -> Self {
+        match expected {
+            Some(expected) if expected == answer => Check::Correct,
+            Some(_) => Check::Wrong,
+            None => Check::Unknown,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Check::Correct => "correct",
+            Check::Wrong => "wrong",
+            Check::Unknown => "unknown",
+            Check::Error => "error",
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Check::Correct => "✔",
+            Check::Wrong => "✘",
+            Check::Unknown => "?",
+            Check::Error => "!",
+        }
+    }
+}
+
+pub fn path(year: u32) -> PathBuf {
+    PathBuf::from(format!("rsc/year{}/answers.toml", year))
+}
+
+pub fn to_answer(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(answer) => Some(answer.clone()),
+        toml::Value::Integer(answer) => Some(answer.to_string()),
+        _ => None,
+    }
+}
+
+/// Parse an answers file, where each `[dayDD]` table holds optional `part1`
+/// and `part2` entries, written as integers or strings.
+pub fn parse(content: &str) -> Result<BTreeMap<u32, Expected>, String> {
+    let table = content.parse::<toml::Table>().map_err(|error| error.to_string())?;
+    table
+        .iter()
+        .map(|(key, value)| {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse::<u32>().ok())
+                .ok_or_else(|| format!("invalid day '{}'", key))?;
+            let part = |name: &str| match value.get(name) {
+                Some(answer) => to_answer(answer)
+                    .map(Some)
+                    .ok_or_else(|| format!("{}.{} must be an integer or a string", key, name)),
+                None => Ok(None),
+            };
+            Ok((day, Expected { part1: part("part1")?, part2: part("part2")? }))
+        })
+        .collect()
+}
+
+/// Load the answers of a year; a missing file means no answer is known.
+pub fn load(year: u32) -> Result<BTreeMap<u32, Expected>, String> {
+    match input::read(path(year)) {
+        Ok(content) => parse(&content).map_err(|error| format!("{}: {}", path(year).display(), error)),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+/// Write answers in the layout of the answers files: a `[dayDD]` table per
+/// day, with answers written as integers when they read back the same.
+pub fn format(answers: &BTreeMap<u32, Expected>) -> String {
+    let value = |answer: &str| match answer.parse::<i64>() {
+        Ok(integer) if integer.to_string() == answer => answer.to_owned(),
+        _ => toml::Value::String(answer.to_owned()).to_string(),
+    };
+    answers
+        .iter()
+        .map(|(day, expected)| {
+            let mut table = format!("[day{:02}]\n", day);
+            for (name, answer) in [("part1", &expected.part1), ("part2", &expected.part2)] {
+                if let Some(answer) = answer {
+                    table.push_str(&format!("{} = {}\n", name, value(answer)));
+                }
+            }
+            table
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Record an accepted answer in the answers file of its year.
+pub fn record(year: u32, day: u32, part: Part, answer: &str) -> Result<(), String> {
+    let mut answers = load(year)?;
+    let expected = answers.entry(day).or_default();
+    match part {
+        Part::One => expected.part1 = Some(answer.to_owned()),
+        Part::Two => expected.part2 = Some(answer.to_owned()),
+    }
+    let path = input::resolve(path(year));
+    std::fs::write(&path, format(&answers)).map_err(|error| format!("{}: {}", path.display(), error))
+}
+
+pub fn expected(year: u32, day: u32) -> Result<Expected, String> {
+    Ok(load(year)?.remove(&day).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_round_trip() {
+        let content = input::read(path(2022)).unwrap();
+        assert_eq!(format(&parse(&content).unwrap()), content);
+
+        let answers = BTreeMap::from([(7, Expected { part1: Some("007".to_owned()), part2: None })]);
+        assert_eq!(format(&answers), "[day07]\npart1 = \"007\"\n");
+    }
+}
@@ -0,0 +1,202 @@
+use std::time::{Duration, Instant};
+
+use crate::error::AocError;
+use crate::input::{self, Source};
+use crate::runner::catch_panic;
+use crate::selector::Selected;
+use crate::solution::{Part, PuzzleParams};
+
+pub struct Options {
+    pub warmup: u32,
+    /// Exact number of measured runs; when absent, runs are repeated until `budget` is spent.
+    pub runs: Option<u32>,
+    pub budget: Duration,
+    pub params: PuzzleParams,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part(Part),
+}
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Parse => f.pad("parse"),
+            Step::Part(part) => part.fmt(f),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let runs = samples.len();
+        let min = samples[0];
+        let median = match runs % 2 {
+            0 => (samples[runs / 2 - 1] + samples[runs / 2]) / 2,
+            _ => samples[runs / 2],
+        };
+        let mean = samples.iter().sum::<Duration>() / runs as u32;
+        let variance = samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+        let stddev = Duration::from_secs_f64(variance.sqrt());
+
+        Self { runs, min, median, mean, stddev }
+    }
+}
+
+pub struct Measurement {
+    pub year: u32,
+    pub day: u32,
+    pub step: Step,
+    pub stats: Stats,
+}
+
+/// Format a duration with the unit that keeps it readable, from ns to s.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos < 1_000 {
+        format!("{} ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.2} µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.2} ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2} s", nanos as f64 / 1e9)
+    }
+}
+
+/// Time repeated runs of `f`, returning their statistics and what the last run gave.
+fn measure<T>(options: &Options, mut f: impl FnMut() -> T) -> (Stats, T) {
+    let start = Instant::now();
+    for _ in 0..options.warmup {
+        std::hint::black_box(f());
+        if start.elapsed() >= options.budget {
+            break;
+        }
+    }
+
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    let last = loop {
+        let run_start = Instant::now();
+        let result = f();
+        samples.push(run_start.elapsed());
+
+        let done = match options.runs {
+            Some(runs) => samples.len() >= runs as usize,
+            None => start.elapsed() >= options.budget,
+        };
+        if done {
+            break result;
+        }
+    };
+
+    (Stats::from_samples(samples), last)
+}
+
+const SEPARATOR: &str = "+------+----+-------+--------+------------+------------+------------+------------+";
+
+/// Benchmark the parsing and the selected parts of each solution, printing
+/// one row per step as soon as it is measured. The parts parse their input
+/// themselves, so their times include the parsing, which is only timed alone
+/// for the days that register their parse step. Steps that fail or panic are
+/// reported after the table and left out of the measurements.
+pub fn run(selected: &[Selected], source: &Source, options: &Options) -> Vec<Measurement> {
+    println!("{}", SEPARATOR);
+    println!("| Year | Day| Step  |  Runs  |    Min     |   Median   |    Mean    |   Stddev   |");
+    println!("{}", SEPARATOR);
+
+    let mut measurements = Vec::new();
+    let mut errors = Vec::new();
+    for Selected { solution, parts } in selected {
+        let (input1, input2) = match input::load(solution, source) {
+            Ok(inputs) => inputs,
+            Err(error) => {
+                errors.push(AocError::new(error).located(solution.year, solution.day, None));
+                continue;
+            }
+        };
+
+        let mut steps = Vec::new();
+        if let Some(parse) = solution.parse {
+            match catch_panic(solution, None, || measure(options, || parse(&input1, &options.params)).0) {
+                Ok(stats) => steps.push((Step::Parse, stats)),
+                Err(error) => errors.push(error),
+            }
+        }
+        for &part in parts {
+            let input = match part {
+                Part::One => &input1,
+                Part::Two => &input2,
+            };
+            match catch_panic(solution, Some(part), || measure(options, || solution.run_part(part, input, &options.params))) {
+                Ok((stats, Ok(_))) => steps.push((Step::Part(part), stats)),
+                Ok((_, Err(error))) | Err(error) => errors.push(error),
+            }
+        }
+
+        if solution.parse.is_none() {
+            println!("| {:04} | {:02} | {:<5} | {:>6} | {:<49} |", solution.year, solution.day, Step::Parse, "-", "no parse step: only timed within the parts");
+        }
+        for (step, stats) in steps {
+            println!(
+                "| {:04} | {:02} | {:<5} | {:>6} | {:>10} | {:>10} | {:>10} | {:>10} |",
+                solution.year,
+                solution.day,
+                step,
+                stats.runs,
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.mean),
+                format_duration(stats.stddev),
+            );
+            measurements.push(Measurement { year: solution.year, day: solution.day, step, stats });
+        }
+    }
+
+    println!("{}", SEPARATOR);
+    println!("The parts parse their input themselves: their times include the parse step.");
+    for error in errors {
+        println!("{}", error);
+    }
+    measurements
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_micros).to_vec();
+        let stats = Stats::from_samples(samples);
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+        assert_eq!(stats.mean, Duration::from_nanos(2500));
+        assert_eq!(stats.stddev.as_nanos(), 1118);
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999 ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.35 µs");
+        assert_eq!(format_duration(Duration::from_micros(4_200)), "4.20 ms");
+        assert_eq!(format_duration(Duration::from_millis(1_500)), "1.50 s");
+    }
+}
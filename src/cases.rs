@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+
+use crate::answers::{self, Expected};
+use crate::input;
+use crate::solution::{Part, PuzzleParams};
+
+/// A named example, as listed in the examples file of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Case {
+    pub name: String,
+    pub input: String,
+    pub expected: Expected,
+    pub params: PuzzleParams,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawCase {
+    input: Option<String>,
+    /// File holding the input, relative to the examples file.
+    file: Option<String>,
+    part1: Option<toml::Value>,
+    part2: Option<toml::Value>,
+    /// Puzzle parameters overriding those of the examples.
+    #[serde(default)]
+    params: toml::Table,
+}
+
+pub fn path(year: u32, day: u32) -> PathBuf {
+    PathBuf::from(format!("rsc/year{}/example/day{:02}.examples.toml", year, day))
+}
+
+/// Parse an examples file, where each table is a case with its `input`, or
+/// the `file` holding it, optional `part1` and `part2` answers, and an
+/// optional `params` table of puzzle parameters.
+pub fn parse(content: &str, dir: &Path) -> Result<Vec<Case>, String> {
+    let cases = toml::from_str::<toml::Table>(content).map_err(|error| error.to_string())?;
+    cases
+        .into_iter()
+        .map(|(name, value)| {
+            let raw = value.try_into::<RawCase>().map_err(|error| format!("{}: {}", name, error))?;
+            let input = match (raw.input, raw.file) {
+                (Some(input), None) => input,
+                (None, Some(file)) => input::read(dir.join(&file)).map_err(|error| format!("{}: {}", name, error))?,
+                _ => return Err(format!("{}: expected either input or file", name)),
+            };
+            let answer = |value: Option<toml::Value>, part: &str| match value {
+                Some(value) => answers::to_answer(&value)
+                    .map(Some)
+                    .ok_or_else(|| format!("{}.{} must be an integer or a string", name, part)),
+                None => Ok(None),
+            };
+            let expected = Expected { part1: answer(raw.part1, "part1")?, part2: answer(raw.part2, "part2")? };
+            let overrides = raw
+                .params
+                .into_iter()
+                .map(|(param, value)| match value {
+                    toml::Value::String(value) => (param, value),
+                    value => (param, value.to_string()),
+                })
+                .collect();
+            let params = PuzzleParams { example: true, overrides };
+            Ok(Case { name, input, expected, params })
+        })
+        .collect()
+}
+
+pub fn load(year: u32, day: u32) -> Result<Vec<Case>, String> {
+    let path = path(year, day);
+    let content = input::read(&path).map_err(|error| error.to_string())?;
+    parse(&content, path.parent().unwrap()).map_err(|error| format!("{}: {}", path.display(), error))
+}
+
+/// Run every case of the examples file of a solution, checking the answers
+/// they give, and report the failing ones all at once.
+pub fn check(solution: &crate::solution::Solution) {
+    let cases = load(solution.year, solution.day).unwrap_or_else(|error| panic!("{}", error));
+    assert!(!cases.is_empty(), "no case in {}", path(solution.year, solution.day).display());
+    let mut failures = Vec::new();
+    for case in &cases {
+        for part in [Part::One, Part::Two] {
+            let Some(expected) = case.expected.get(part) else {
+                continue;
+            };
+            match solution.run_part(part, &input::normalize(&case.input, solution.whitespace), &case.params) {
+                Ok(answer) if answer == expected => (),
+                Ok(answer) => failures.push(format!("{} {}: expected {}, got {}", case.name, part, expected, answer)),
+                Err(error) => failures.push(format!("{} {}: {}", case.name, part, error)),
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_examples_file() {
+        let content = "[first]\ninput = \"abc\"\npart1 = 7\n\n[second]\ninput = \"def\"\npart2 = \"x\"\n";
+        let cases = parse(content, Path::new(".")).unwrap();
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].name, "first");
+        assert_eq!(cases[0].expected, Expected { part1: Some("7".to_owned()), part2: None });
+        assert_eq!(cases[1].expected.get(Part::Two), Some("x"));
+
+        let from_file = parse("[larger]\nfile = \"day09.txt\"\n", Path::new("rsc/year2022/example")).unwrap();
+        assert!(from_file[0].input.starts_with("R 4\n"));
+        assert!(from_file[0].params.example);
+
+        let with_params = parse("[row]\ninput = \"\"\nparams = { y = 12, layout = \"input\" }\n", Path::new(".")).unwrap();
+        assert_eq!(with_params[0].params.get("y", 10, 2_000_000), Ok(12));
+        assert_eq!(with_params[0].params.get("layout", String::new(), String::new()).unwrap(), "input");
+        assert!(parse("[neither]\npart1 = 1\n", Path::new(".")).is_err());
+    }
+}
@@ -0,0 +1,244 @@
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config::{self, Config};
+use crate::input;
+use crate::storage::Storage;
+
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"), " (advent of code runner)");
+
+/// Seconds between two requests when the configuration does not say.
+const DEFAULT_DELAY: u64 = 3;
+
+/// Talks to adventofcode.com, or to the server configured in its place,
+/// leaving at least the configured delay between two requests, even when
+/// they come from different runs.
+pub struct Client {
+    agent: ureq::Agent,
+    url: String,
+    session: String,
+    delay: Duration,
+    last_request: Option<SystemTime>,
+    /// File keeping the time of the last request, in milliseconds since the
+    /// epoch, for the next runs: in `input_dir`, or in `rsc` without it.
+    stamp: PathBuf,
+}
+
+impl Client {
+    pub fn new(config: &Config) -> Result<Self, String> {
+        let session = config
+            .session
+            .clone()
+            .ok_or_else(|| format!("no session token: set AOC_SESSION or `session` in {}", config::path().display()))?;
+        Ok(Self {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).timeout(Duration::from_secs(30)).build(),
+            url: config.url.as_deref().unwrap_or(config::DEFAULT_URL).trim_end_matches('/').to_owned(),
+            session,
+            delay: Duration::from_secs(config.delay.unwrap_or(DEFAULT_DELAY)),
+            last_request: None,
+            stamp: config.input_dir.clone().unwrap_or_else(|| input::resolve("rsc")).join(".last_request"),
+        })
+    }
+
+    /// Wait until the delay has passed since the last request of this run or
+    /// of a previous one. The stamp is only a courtesy to the site: failing
+    /// to read or write it does not stop the request.
+    fn throttle(&mut self) {
+        let stamped = std::fs::read_to_string(&self.stamp)
+            .ok()
+            .and_then(|content| content.trim().parse().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        if let Some(last_request) = self.last_request.max(stamped) {
+            let elapsed = SystemTime::now().duration_since(last_request).unwrap_or_default();
+            std::thread::sleep(self.delay.saturating_sub(elapsed));
+        }
+        let now = SystemTime::now();
+        self.last_request = Some(now);
+        let millis = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+        if let Some(parent) = self.stamp.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let _ = std::fs::write(&self.stamp, millis.to_string());
+    }
+
+    fn get(&mut self, path: &str) -> Result<String, String> {
+        self.throttle();
+        let url = format!("{}{}", self.url, path);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|error| describe(&url, error))?;
+        response.into_string().map_err(|error| format!("{}: {}", url, error))
+    }
+
+    fn post(&mut self, path: &str, form: &[(&str, &str)]) -> Result<String, String> {
+        self.throttle();
+        let url = format!("{}{}", self.url, path);
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(form)
+            .map_err(|error| describe(&url, error))?;
+        response.into_string().map_err(|error| format!("{}: {}", url, error))
+    }
+
+    pub fn input(&mut self, year: u32, day: u32) -> Result<String, String> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+
+    pub fn puzzle(&mut self, year: u32, day: u32) -> Result<String, String> {
+        self.get(&format!("/{}/day/{}", year, day))
+    }
+
+    /// Submit an answer, returning the page telling whether it is right.
+    pub fn answer(&mut self, year: u32, day: u32, level: u8, answer: &str) -> Result<String, String> {
+        self.post(&format!("/{}/day/{}/answer", year, day), &[("level", &level.to_string()), ("answer", answer)])
+    }
+}
+
+fn describe(url: &str, error: ureq::Error) -> String {
+    match error {
+        ureq::Error::Status(status, response) => {
+            let body = response.into_string().unwrap_or_default();
+            match body.lines().next().map(str::trim).filter(|line| !line.is_empty()) {
+                Some(line) => format!("{}: {} {}", url, status, line),
+                None => format!("{}: {}", url, status),
+            }
+        }
+        ureq::Error::Transport(error) => format!("{}: {}", url, error),
+    }
+}
+
+/// Where the input of a day is, and whether it was already there.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Download the input of a day where `storage` keeps the plain inputs, unless
+/// it already has it, plain or encrypted.
+pub fn fetch(client: &mut Client, storage: &Storage, year: u32, day: u32) -> Result<Fetched, String> {
+    if let Some(path) = storage.cached(year, day) {
+        return Ok(Fetched::Cached(path));
+    }
+    let input = client.input(year, day)?;
+    let path = storage.plain_path(year, day);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|error| format!("{}: {}", parent.display(), error))?;
+    }
+    std::fs::write(&path, input).map_err(|error| format!("{}: {}", path.display(), error))?;
+    Ok(Fetched::Downloaded(path))
+}
+
+/// A local HTTP server answering each connection with the next canned
+/// response, standing in for adventofcode.com.
+#[cfg(test)]
+pub mod stub {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    /// Start the server and return its URL, along with a handle giving the
+    /// requests it received, each as its head and body.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut content = vec![0; content_length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                requests.push(request);
+
+                let response = format!(
+                    "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fetch_once() {
+        let (url, server) = stub::serve(vec![(200, "1000\n2000\n"), (404, "Not Found\n")]);
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let config = Config {
+            session: Some("53616c7465".to_owned()),
+            url: Some(url),
+            delay: Some(0),
+            input_dir: Some(dir.clone()),
+            ..Config::default()
+        };
+        let mut client = Client::new(&config).unwrap();
+        let storage = Storage::new(None, Some(dir.clone()));
+        let path = dir.join("year2022/day01.txt");
+
+        assert_eq!(fetch(&mut client, &storage, 2022, 1), Ok(Fetched::Downloaded(path.clone())));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+        assert_eq!(fetch(&mut client, &storage, 2022, 1), Ok(Fetched::Cached(path)));
+
+        assert!(fetch(&mut client, &storage, 2022, 25).unwrap_err().ends_with("/2022/day/25/input: 404 Not Found"));
+        assert!(!dir.join("year2022/day25.txt").exists());
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=53616c7465\r\n"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn delay_across_runs() {
+        let (url, server) = stub::serve(vec![(200, "1\n"), (200, "2\n")]);
+        let dir = std::env::temp_dir().join(format!("aoc-delay-{}", std::process::id()));
+        let config = Config {
+            session: Some("53616c7465".to_owned()),
+            url: Some(url),
+            delay: Some(1),
+            input_dir: Some(dir.clone()),
+            ..Config::default()
+        };
+        Client::new(&config).unwrap().input(2022, 1).unwrap();
+
+        // A new client, as in the next run, still waits for the first request.
+        let start = std::time::Instant::now();
+        Client::new(&config).unwrap().input(2022, 2).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(900));
+        server.join().unwrap();
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn missing_session() {
+        assert!(Client::new(&Config::default()).is_err());
+    }
+}
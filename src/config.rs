@@ -0,0 +1,81 @@
+/// Local settings, kept out of the repository since they hold the session token.
+pub const PATH: &str = "aoc.toml";
+
+/// Where the settings are read from: `AOC_CONFIG` when set, otherwise
+/// `aoc.toml` in the source root, whatever the current directory.
+pub fn path() -> std::path::PathBuf {
+    match std::env::var_os("AOC_CONFIG") {
+        Some(path) => path.into(),
+        None => crate::input::source_root().join(PATH),
+    }
+}
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+/// Settings read from `aoc.toml`, each overridden by its environment variable.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Value of the `session` cookie of adventofcode.com (`AOC_SESSION`).
+    pub session: Option<String>,
+    /// Base URL of the site, to point at a local server when testing (`AOC_URL`).
+    pub url: Option<String>,
+    /// Minimum number of seconds between two requests (`AOC_DELAY`).
+    pub delay: Option<u64>,
+    /// Directory holding `rsc`, when it is not the checkout the binary was built from (`AOC_DATA`).
+    pub data: Option<std::path::PathBuf>,
+    /// Hex key of the encrypted puzzle inputs (`AOC_INPUT_KEY`).
+    pub input_key: Option<String>,
+    /// Directory keeping the puzzle inputs out of the repository (`AOC_INPUT_DIR`).
+    pub input_dir: Option<std::path::PathBuf>,
+}
+
+impl Config {
+    pub fn parse(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|error| error.to_string())
+    }
+
+    /// Load the settings file if it exists, then apply the environment
+    /// variables.
+    pub fn load() -> Result<Self, String> {
+        let path = path();
+        let mut config = match std::fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content).map_err(|error| format!("{}: {}", path.display(), error))?,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(error) => return Err(format!("{}: {}", path.display(), error)),
+        };
+        if let Ok(session) = std::env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(url) = std::env::var("AOC_URL") {
+            config.url = Some(url);
+        }
+        if let Ok(delay) = std::env::var("AOC_DELAY") {
+            config.delay = Some(delay.parse().map_err(|_| format!("invalid AOC_DELAY '{}'", delay))?);
+        }
+        if let Some(data) = std::env::var_os("AOC_DATA") {
+            config.data = Some(data.into());
+        }
+        if let Ok(key) = std::env::var("AOC_INPUT_KEY") {
+            config.input_key = Some(key);
+        }
+        if let Some(dir) = std::env::var_os("AOC_INPUT_DIR") {
+            config.input_dir = Some(dir.into());
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let config = Config::parse("session = \"53616c7465\"\nurl = \"http://127.0.0.1:8080\"\n").unwrap();
+        assert_eq!(config.session.as_deref(), Some("53616c7465"));
+        assert_eq!(config.url.as_deref(), Some("http://127.0.0.1:8080"));
+        assert_eq!(config.delay, None);
+        assert!(Config::parse("sesion = \"53616c7465\"").is_err());
+    }
+}
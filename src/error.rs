@@ -0,0 +1,105 @@
+use std::fmt;
+
+use crate::solution::Part;
+
+/// Why a part could not give an answer. The location is filled in as the
+/// error goes up: the line by the day parsing the input, then the year, day
+/// and part by the solution running it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AocError {
+    pub year: Option<u32>,
+    pub day: Option<u32>,
+    pub part: Option<Part>,
+    /// Line of the input, starting at 1.
+    pub line: Option<usize>,
+    pub cause: String,
+}
+
+impl AocError {
+    pub fn new(cause: impl fmt::Display) -> Self {
+        Self { year: None, day: None, part: None, line: None, cause: cause.to_string() }
+    }
+
+    pub fn at_line(self, line: usize) -> Self {
+        Self { line: self.line.or(Some(line)), ..self }
+    }
+
+    /// Set the puzzle the error happened in, keeping a location already known.
+    pub fn located(self, year: u32, day: u32, part: Option<Part>) -> Self {
+        Self {
+            year: self.year.or(Some(year)),
+            day: self.day.or(Some(day)),
+            part: self.part.or(part),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(year) = self.year {
+            write!(f, "{:04}", year)?;
+            if let Some(day) = self.day {
+                write!(f, ".{:02}", day)?;
+            }
+            if let Some(part) = self.part {
+                write!(f, ".{}", part)?;
+            }
+            write!(f, ": ")?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        write!(f, "{}", self.cause)
+    }
+}
+
+impl std::error::Error for AocError {}
+
+/// What a part function returns: either its answer, or a result for the
+/// days whose parsing can fail.
+pub trait Answer {
+    fn into_answer(self) -> Result<String, AocError>;
+}
+
+impl Answer for String {
+    fn into_answer(self) -> Result<String, AocError> {
+        Ok(self)
+    }
+}
+
+impl Answer for Result<String, AocError> {
+    fn into_answer(self) -> Result<String, AocError> {
+        self
+    }
+}
+
+/// Parse each line of the input, tagging errors with the line they come from.
+pub fn parse_lines<T>(input: &str, parse: impl Fn(&str) -> Result<T, AocError>) -> Result<Vec<T>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse(line).map_err(|error| error.at_line(index + 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let error = AocError::new("invalid flow rate 'x'");
+        assert_eq!(error.to_string(), "invalid flow rate 'x'");
+        let error = error.at_line(3).located(2022, 16, Some(Part::One));
+        assert_eq!(error.to_string(), "2022.16.p1: line 3: invalid flow rate 'x'");
+        assert_eq!(AocError::new("missing input").located(2022, 1, None).to_string(), "2022.01: missing input");
+    }
+
+    #[test]
+    fn line_numbers() {
+        let parse = |line: &str| line.parse::<u32>().map_err(|_| AocError::new(format!("not a number '{}'", line)));
+        assert_eq!(parse_lines("1\n2\n", parse), Ok(vec![1, 2]));
+        assert_eq!(parse_lines("1\nx\n3", parse).unwrap_err().line, Some(2));
+    }
+}
@@ -0,0 +1,166 @@
+use std::path::{Path, PathBuf};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::input;
+use crate::solution::Part;
+
+/// The example given in the description of a part, and its answer.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Example {
+    pub input: Option<String>,
+    pub answer: Option<String>,
+}
+
+lazy_static! {
+    static ref ARTICLE: Regex = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    static ref PRE: Regex = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    static ref ANSWER: Regex = Regex::new(r"(?s)<code><em>(.*?)</em></code>").unwrap();
+    static ref TAG: Regex = Regex::new(r"<[^>]*>").unwrap();
+}
+
+/// The text of an HTML fragment, without its tags.
+fn text(html: &str) -> String {
+    TAG.replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Extract the example of each part described in a puzzle page: the first
+/// code block of its description, and the last emphasised code, which is
+/// where the puzzles give the answer of the example.
+pub fn extract(page: &str) -> Vec<Example> {
+    ARTICLE
+        .captures_iter(page)
+        .map(|article| Example {
+            input: PRE.captures(&article[1]).map(|pre| text(&pre[1])),
+            answer: ANSWER.captures_iter(&article[1]).last().map(|answer| text(&answer[1])),
+        })
+        .collect()
+}
+
+/// The example files of a day: a single one when both parts share it.
+fn files(year: u32, day: u32, examples: &[Example]) -> Vec<(PathBuf, String)> {
+    let input = |index: usize| examples.get(index).and_then(|example| example.input.clone());
+    match (input(0), input(1)) {
+        (None, _) => Vec::new(),
+        (Some(input1), Some(input2)) if input1 != input2 => vec![
+            (input::example_path(year, day, Some(Part::One)), input1),
+            (input::example_path(year, day, Some(Part::Two)), input2),
+        ],
+        (Some(input), _) => vec![(input::example_path(year, day, None), input)],
+    }
+}
+
+/// The `test_example_aoc!` line for the answers found, with the `0000`
+/// placeholder for the missing ones.
+pub fn proposal(examples: &[Example]) -> String {
+    let answer = |index: usize| match examples.get(index).and_then(|example| example.answer.as_deref()) {
+        Some(answer) if answer.parse::<i64>().is_ok() => answer.to_owned(),
+        Some(answer) => format!("{:?}", answer),
+        None => "0000".to_owned(),
+    };
+    format!("crate::test_example_aoc!({}, {});", answer(0), answer(1))
+}
+
+const PLACEHOLDER: &str = "crate::test_example_aoc!(0000, 0000);";
+
+/// Write the example files of a day under `data`, keeping those that already
+/// have content, and put the proposed expected answers in the day module
+/// under `source` when it still has the placeholder of the template.
+pub fn save(source: &Path, data: &Path, year: u32, day: u32, examples: &[Example]) -> Result<(), String> {
+    let files = files(year, day, examples);
+    if files.is_empty() {
+        return Err("no example found in the page".to_owned());
+    }
+    for (path, content) in &files {
+        let path = data.join(path);
+        if std::fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+            println!("kept {}", path.display());
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|error| format!("{}: {}", parent.display(), error))?;
+        }
+        std::fs::write(&path, content).map_err(|error| format!("{}: {}", path.display(), error))?;
+        println!("wrote {}", path.display());
+    }
+    let shared = data.join(input::example_path(year, day, None));
+    if files.len() == 2 && std::fs::metadata(&shared).is_ok_and(|metadata| metadata.len() > 0) {
+        println!("{} is read first: remove it to use the examples of each part", shared.display());
+    }
+
+    let proposal = proposal(examples);
+    println!("{}", proposal);
+    let module = source.join(format!("src/year{}/day{:02}.rs", year, day));
+    if let Ok(source) = std::fs::read_to_string(&module) {
+        if source.contains(PLACEHOLDER) {
+            std::fs::write(&module, source.replacen(PLACEHOLDER, &proposal, 1))
+                .map_err(|error| format!("{}: {}", module.display(), error))?;
+            println!("wrote {}", module.display());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2><p>For example:</p>
+<pre><code>1000
+2000
+
+4000
+</code></pre>
+<p>This Elf is carrying <em>the most Calories</em>: <code><em>24000</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>71300</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><pre><code>a &lt;-&gt; <em>b</em>
+</code></pre><p>In the example above, the top three Elves are carrying <code><em>45000</em></code> Calories.</p></article>
+</main>"#;
+
+    #[test]
+    fn extract_examples() {
+        let examples = extract(PAGE);
+        assert_eq!(
+            examples,
+            vec![
+                Example { input: Some("1000\n2000\n\n4000\n".to_owned()), answer: Some("24000".to_owned()) },
+                Example { input: Some("a <-> b\n".to_owned()), answer: Some("45000".to_owned()) },
+            ]
+        );
+        assert_eq!(proposal(&examples), "crate::test_example_aoc!(24000, 45000);");
+        assert_eq!(proposal(&examples[..1]), "crate::test_example_aoc!(24000, 0000);");
+        assert_eq!(files(2022, 1, &examples).len(), 2);
+        assert_eq!(files(2022, 1, &examples[..1])[0].0, PathBuf::from("rsc/year2022/example/day01.txt"));
+    }
+
+    #[test]
+    fn save_examples() {
+        let root = std::env::temp_dir().join(format!("aoc-example-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("src/year2022")).unwrap();
+        std::fs::write(root.join("src/year2022/day01.rs"), format!("crate::run!();\n\n{}\n", PLACEHOLDER)).unwrap();
+        let examples = extract(PAGE);
+        let data = root.join("data");
+
+        save(&root, &data, 2022, 1, &examples[..1]).unwrap();
+        assert_eq!(std::fs::read_to_string(data.join("rsc/year2022/example/day01.txt")).unwrap(), "1000\n2000\n\n4000\n");
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/year2022/day01.rs")).unwrap(),
+            "crate::run!();\n\ncrate::test_example_aoc!(24000, 0000);\n"
+        );
+
+        std::fs::write(data.join("rsc/year2022/example/day01.txt"), "edited").unwrap();
+        save(&root, &data, 2022, 1, &examples[..1]).unwrap();
+        assert_eq!(std::fs::read_to_string(data.join("rsc/year2022/example/day01.txt")).unwrap(), "edited");
+        assert!(save(&root, &data, 2022, 1, &[Example::default()]).is_err());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
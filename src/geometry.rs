@@ -0,0 +1,309 @@
+//! Points and directions for the puzzles on a plane or in space. On a plane,
+//! `y` grows downwards, as the rows of a map do.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// The signed integers points are made of.
+pub trait Coordinate:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+}
+
+macro_rules! coordinate {
+    ($($type:ty),*) => {
+        $(
+            impl Coordinate for $type {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn abs(self) -> Self {
+                    <$type>::abs(self)
+                }
+
+                fn signum(self) -> Self {
+                    <$type>::signum(self)
+                }
+            }
+        )*
+    };
+}
+
+coordinate!(i8, i16, i32, i64, isize);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Coordinate> Point2<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The distance when moving diagonally counts as one step.
+    pub fn chebyshev(self, other: Self) -> T {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// The sign of each coordinate: a step towards the point from the origin.
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        Dir8::ALL.into_iter().map(move |dir| self + dir.offset())
+    }
+}
+
+impl<T: Coordinate> Point3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    /// The points sharing a face with this one.
+    pub fn neighbours6(self) -> impl Iterator<Item = Self> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        [(-one, zero, zero), (one, zero, zero), (zero, -one, zero), (zero, one, zero), (zero, zero, -one), (zero, zero, one)]
+            .into_iter()
+            .map(move |(x, y, z)| self + Self::new(x, y, z))
+    }
+}
+
+macro_rules! arithmetic {
+    ($point:ident, $($field:ident),*) => {
+        impl<T: Coordinate> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $($field: self.$field + other.$field),* }
+            }
+        }
+
+        impl<T: Coordinate> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $($field: self.$field - other.$field),* }
+            }
+        }
+
+        impl<T: Coordinate> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, factor: T) -> Self {
+                Self { $($field: self.$field * factor),* }
+            }
+        }
+
+        impl<T: Coordinate> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),* }
+            }
+        }
+
+        impl<T: Coordinate> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Coordinate> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+arithmetic!(Point2, x, y);
+arithmetic!(Point3, x, y, z);
+
+/// The 4 orthogonal directions, clockwise from facing right.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl Dir4 {
+    pub const ALL: [Dir4; 4] = [Dir4::Right, Dir4::Down, Dir4::Left, Dir4::Up];
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    /// A step in this direction.
+    pub fn offset<T: Coordinate>(self) -> Point2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match self {
+            Dir4::Right => Point2::new(one, zero),
+            Dir4::Down => Point2::new(zero, one),
+            Dir4::Left => Point2::new(-one, zero),
+            Dir4::Up => Point2::new(zero, -one),
+        }
+    }
+}
+
+/// Parse `U`, `D`, `L` and `R`, arrows `^`, `v`, `<` and `>`, or `N`, `S`,
+/// `W` and `E`.
+impl TryFrom<char> for Dir4 {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'R' | '>' | 'E' => Ok(Dir4::Right),
+            'D' | 'v' | 'S' => Ok(Dir4::Down),
+            'L' | '<' | 'W' => Ok(Dir4::Left),
+            'U' | '^' | 'N' => Ok(Dir4::Up),
+            _ => Err(format!("invalid direction {:?}", c)),
+        }
+    }
+}
+
+impl std::str::FromStr for Dir4 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Dir4::try_from(c),
+            _ => Err(format!("invalid direction {:?}", s)),
+        }
+    }
+}
+
+/// The 8 directions of the compass, clockwise from north.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [Dir8::N, Dir8::NE, Dir8::E, Dir8::SE, Dir8::S, Dir8::SW, Dir8::W, Dir8::NW];
+
+    /// Turned by an eighth of a turn.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn offset<T: Coordinate>(self) -> Point2<T> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        match self {
+            Dir8::N => Point2::new(zero, -one),
+            Dir8::NE => Point2::new(one, -one),
+            Dir8::E => Point2::new(one, zero),
+            Dir8::SE => Point2::new(one, one),
+            Dir8::S => Point2::new(zero, one),
+            Dir8::SW => Point2::new(-one, one),
+            Dir8::W => Point2::new(-one, zero),
+            Dir8::NW => Point2::new(-one, -one),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::Right => Dir8::E,
+            Dir4::Down => Dir8::S,
+            Dir4::Left => Dir8::W,
+            Dir4::Up => Dir8::N,
+        }
+    }
+}
+
+/// Parse `N`, `NE`, … `NW`, or any of the orthogonal directions `Dir4` parses.
+impl std::str::FromStr for Dir8 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Dir8::NE),
+            "SE" => Ok(Dir8::SE),
+            "SW" => Ok(Dir8::SW),
+            "NW" => Ok(Dir8::NW),
+            _ => s.parse::<Dir4>().map(Dir8::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn points() {
+        let (a, b) = (Point2::new(1, 2), Point2::new(4, -2));
+        assert_eq!(a + b, Point2::new(5, 0));
+        assert_eq!(a - b, Point2::new(-3, 4));
+        assert_eq!(a * 3, Point2::new(3, 6));
+        assert_eq!((a.manhattan(b), a.chebyshev(b)), (7, 4));
+        assert_eq!((b - a).signum(), Point2::new(1, -1));
+        assert!(a.neighbours8().all(|n| n.chebyshev(a) == 1));
+
+        let c = Point3::new(1i64, 2, 3);
+        assert_eq!(c + c * 2 - Point3::new(3, 3, 3), Point3::new(0, 3, 6));
+        assert_eq!(c.manhattan(Point3::default()), 6);
+        assert_eq!(c.neighbours6().filter(|n| n.manhattan(c) == 1).count(), 6);
+    }
+
+    #[test]
+    fn directions() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Right.turn_left(), Dir4::Up);
+        assert_eq!("UDLR".chars().map(|c| Dir4::try_from(c).unwrap()).collect::<Vec<_>>(), [Dir4::Up, Dir4::Down, Dir4::Left, Dir4::Right]);
+        assert_eq!("^v<>".chars().map(|c| Dir4::try_from(c).unwrap()).collect::<Vec<_>>(), [Dir4::Up, Dir4::Down, Dir4::Left, Dir4::Right]);
+        assert_eq!("W".parse::<Dir4>(), Ok(Dir4::Left));
+        assert!("X".parse::<Dir4>().is_err());
+
+        assert_eq!(Dir8::NW.turn_right(), Dir8::N);
+        assert_eq!(Dir8::N.turn_left(), Dir8::NW);
+        assert_eq!("SW".parse::<Dir8>(), Ok(Dir8::SW));
+        assert_eq!("E".parse::<Dir8>(), Ok(Dir8::E));
+        assert!(Dir4::ALL.iter().all(|&dir| Dir8::from(dir).offset::<i32>() == dir.offset()));
+    }
+}
@@ -0,0 +1,180 @@
+//! Weighted directed graphs for the network puzzles, whose nodes are numbered
+//! from 0, and the shortest distances between their nodes.
+
+use std::collections::HashMap;
+
+use crate::search;
+
+/// The shortest distance from each node to each node, `None` when there is
+/// no path.
+pub type Distances = Vec<Vec<Option<u32>>>;
+
+/// Labels numbered in the order they are first seen.
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    indices: HashMap<String, usize>,
+    labels: Vec<String>,
+}
+
+impl Interner {
+    /// The index of a label, numbering it if it is new.
+    pub fn intern(&mut self, label: &str) -> usize {
+        if let Some(&index) = self.indices.get(label) {
+            return index;
+        }
+        self.indices.insert(label.to_owned(), self.labels.len());
+        self.labels.push(label.to_owned());
+        self.labels.len() - 1
+    }
+
+    pub fn get(&self, label: &str) -> Option<usize> {
+        self.indices.get(label).copied()
+    }
+
+    pub fn label(&self, index: usize) -> &str {
+        &self.labels[index]
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Graph {
+    /// The nodes each node leads to, and the weight of the edge.
+    edges: Vec<Vec<(usize, u32)>>,
+}
+
+impl Graph {
+    pub fn new(nodes: usize) -> Self {
+        Self { edges: vec![Vec::new(); nodes] }
+    }
+
+    /// The graph of labelled nodes, each leading to the nodes of its
+    /// neighbours with an edge of weight 1, and the index of each label.
+    pub fn from_adjacency<'a, N>(adjacency: impl IntoIterator<Item = (&'a str, N)>) -> (Self, Interner)
+    where
+        N: IntoIterator<Item = &'a str>,
+    {
+        let mut graph = Self::default();
+        let mut labels = Interner::default();
+        for (label, neighbours) in adjacency {
+            let from = labels.intern(label);
+            for neighbour in neighbours {
+                let to = labels.intern(neighbour);
+                graph.edges.resize_with(labels.len(), Vec::new);
+                graph.add_edge(from, to, 1);
+            }
+            graph.edges.resize_with(labels.len(), Vec::new);
+        }
+        (graph, labels)
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: u32) {
+        self.edges[from].push((to, weight));
+    }
+
+    /// The shortest distances between all the nodes, by Floyd–Warshall, in
+    /// O(n³): for small or dense graphs.
+    pub fn floyd_warshall(&self) -> Distances {
+        let n = self.len();
+        let mut distances = vec![vec![None; n]; n];
+        for (from, edges) in self.edges.iter().enumerate() {
+            distances[from][from] = Some(0);
+            for &(to, weight) in edges {
+                if distances[from][to].is_none_or(|distance| weight < distance) {
+                    distances[from][to] = Some(weight);
+                }
+            }
+        }
+
+        for via in 0..n {
+            // Paths through `via` do not shorten those from `via`.
+            let from_via = distances[via].clone();
+            for row in &mut distances {
+                let Some(first) = row[via] else {
+                    continue;
+                };
+                for (distance, second) in row.iter_mut().zip(&from_via) {
+                    let Some(second) = second else {
+                        continue;
+                    };
+                    if distance.is_none_or(|distance| first + second < distance) {
+                        *distance = Some(first + second);
+                    }
+                }
+            }
+        }
+        distances
+    }
+
+    /// The graph between the `keep` nodes only, node `i` being `keep[i]`, with
+    /// an edge for the shortest path from each to each other it leads to.
+    pub fn compress(&self, keep: &[usize]) -> Graph {
+        let mut compressed = Graph::new(keep.len());
+        for (from, &node) in keep.iter().enumerate() {
+            let costs = search::dijkstra_all([node], |&node| self.edges[node].iter().copied());
+            for (to, other) in keep.iter().enumerate() {
+                if let Some(&cost) = costs.get(other).filter(|_| from != to) {
+                    compressed.add_edge(from, to, cost);
+                }
+            }
+        }
+        compressed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// AA - BB - CC - DD, and a shortcut from AA to DD weighing 5.
+    fn network() -> (Graph, Interner) {
+        let (mut graph, labels) = Graph::from_adjacency([
+            ("AA", vec!["BB"]),
+            ("BB", vec!["AA", "CC"]),
+            ("CC", vec!["BB", "DD"]),
+            ("DD", vec!["CC"]),
+        ]);
+        graph.add_edge(0, 3, 5);
+        (graph, labels)
+    }
+
+    #[test]
+    fn interning() {
+        let (graph, labels) = Graph::from_adjacency([("BB", ["CC", "AA"]), ("CC", ["BB", "EE"])]);
+        assert_eq!((graph.len(), labels.len()), (4, 4));
+        assert_eq!((labels.get("AA"), labels.get("EE"), labels.get("ZZ")), (Some(2), Some(3), None));
+        assert_eq!(labels.label(1), "CC");
+        let distances = graph.floyd_warshall();
+        assert_eq!(distances[0], [Some(0), Some(1), Some(1), Some(2)]);
+        assert_eq!(distances[3], [None, None, None, Some(0)]);
+    }
+
+    #[test]
+    fn all_pairs() {
+        let (graph, _) = network();
+        let distances = graph.floyd_warshall();
+        assert_eq!(distances[0], [Some(0), Some(1), Some(2), Some(3)]);
+        assert_eq!(distances[3][0], Some(3));
+
+        let mut one_way = Graph::new(3);
+        one_way.add_edge(0, 1, 2);
+        one_way.add_edge(1, 2, 2);
+        assert_eq!(one_way.floyd_warshall(), vec![vec![Some(0), Some(2), Some(4)], vec![None, Some(0), Some(2)], vec![None, None, Some(0)]]);
+    }
+
+    #[test]
+    fn compression() {
+        let (graph, labels) = network();
+        let keep = ["AA", "DD"].map(|label| labels.get(label).unwrap());
+        let compressed = graph.compress(&keep);
+        assert_eq!(compressed.len(), 2);
+        assert_eq!(compressed.floyd_warshall(), vec![vec![Some(0), Some(3)], vec![Some(3), Some(0)]]);
+    }
+}
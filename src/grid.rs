@@ -0,0 +1,515 @@
+//! Grids of cells for the 2D puzzles: a rectangular one, stored row after
+//! row, for the puzzles given as character maps, one growing in any direction
+//! and a sparse one for the simulations without bounds. Positions are
+//! `(row, col)`.
+
+use std::collections::HashMap;
+use std::ops::{Index, IndexMut, Range};
+
+use crate::error::AocError;
+
+pub type Position = (usize, usize);
+
+/// Offsets to the 4 orthogonal neighbours.
+const NEIGHBOURS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// What all the grids have, positions being signed.
+pub trait Cells<T> {
+    /// The cell at a position, `None` out of the bounds or where there is none.
+    fn get(&self, position: (isize, isize)) -> Option<&T>;
+
+    /// The smallest rectangle holding all the cells.
+    fn bounds(&self) -> Bounds;
+
+    /// How many cells have a value.
+    fn count(&self, value: &T) -> usize
+    where
+        T: PartialEq;
+
+    /// Give a value to the cell at a position.
+    fn set(&mut self, position: (isize, isize), value: T);
+
+    /// Set the cells `f` gives for the characters of a map, a line per row
+    /// from row 0.
+    fn set_parsed(&mut self, input: &str, mut f: impl FnMut(char) -> Option<T>)
+    where
+        Self: Sized,
+    {
+        for (row, line) in input.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                if let Some(value) = f(c) {
+                    self.set((row as isize, col as isize), value);
+                }
+            }
+        }
+    }
+}
+
+/// A rectangle of positions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Bounds {
+    pub rows: Range<isize>,
+    pub cols: Range<isize>,
+}
+
+impl Bounds {
+    pub fn area(&self) -> usize {
+        self.rows.len() * self.cols.len()
+    }
+
+    pub fn contains(&self, (row, col): (isize, isize)) -> bool {
+        self.rows.contains(&row) && self.cols.contains(&col)
+    }
+
+    /// The smallest rectangle holding this one and a position.
+    fn including(&self, (row, col): (isize, isize)) -> Self {
+        if self.area() == 0 {
+            return Self { rows: row..row + 1, cols: col..col + 1 };
+        }
+        Self {
+            rows: self.rows.start.min(row)..self.rows.end.max(row + 1),
+            cols: self.cols.start.min(col)..self.cols.end.max(col + 1),
+        }
+    }
+
+    /// Every position, row after row.
+    pub fn positions(&self) -> impl Iterator<Item = (isize, isize)> {
+        let cols = self.cols.clone();
+        self.rows.clone().flat_map(move |row| cols.clone().map(move |col| (row, col)))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(height: usize, width: usize, fill: T) -> Self {
+        Self { height, width, cells: vec![fill; height * width] }
+    }
+
+    /// Parse a character map, a line per row. Lines shorter than the longest
+    /// one are padded with spaces.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Self {
+        Self::try_parse(input, |c| Ok(f(c))).expect("every cell parses")
+    }
+
+    /// Parse a character map as `parse` does, with cells that can be invalid,
+    /// tagging the errors with their line.
+    pub fn try_parse(input: &str, mut f: impl FnMut(char) -> Result<T, AocError>) -> Result<Self, AocError> {
+        let width = input.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+        let mut cells = Vec::new();
+        for (index, line) in input.lines().enumerate() {
+            let padding = std::iter::repeat_n(' ', width - line.chars().count());
+            for c in line.chars().chain(padding) {
+                cells.push(f(c).map_err(|error| error.at_line(index + 1))?);
+            }
+        }
+        Ok(Self { height: cells.len() / width.max(1), width, cells })
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    fn contains(&self, (row, col): (isize, isize)) -> Option<Position> {
+        let inside = (0..self.height as isize).contains(&row) && (0..self.width as isize).contains(&col);
+        inside.then_some((row as usize, col as usize))
+    }
+
+    /// The cell at a position that may be out of the grid.
+    pub fn get(&self, position: (isize, isize)) -> Option<&T> {
+        self.contains(position).map(|position| &self[position])
+    }
+
+    /// The position in the grid of any position, the grid repeating itself
+    /// on all sides. Panics when the grid is empty.
+    pub fn wrap(&self, (row, col): (isize, isize)) -> Position {
+        assert!(!self.cells.is_empty(), "no position to wrap to in an empty grid");
+        (row.rem_euclid(self.height as isize) as usize, col.rem_euclid(self.width as isize) as usize)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every position, row after row.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Every cell with its position, row after row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The position of the first cell, row after row, matching `predicate`.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Position> {
+        self.iter().find(|(_, cell)| predicate(cell)).map(|(position, _)| position)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { height: self.height, width: self.width, cells: self.cells.iter().map(f).collect() }
+    }
+
+    /// The orthogonal neighbours of a position that are in the grid.
+    pub fn neighbours4(&self, (row, col): Position) -> impl Iterator<Item = Position> + '_ {
+        NEIGHBOURS
+            .iter()
+            .filter_map(move |&(drow, dcol)| self.contains((row as isize + drow, col as isize + dcol)))
+    }
+
+    /// The grid as seen after turning or flipping it, without copying it.
+    pub fn view(&self, transform: Transform) -> View<'_, T> {
+        View { grid: self, transform }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): Position) -> &T {
+        assert!(col < self.width, "column {} out of a grid {} wide", col, self.width);
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, (row, col): Position) -> &mut T {
+        assert!(col < self.width, "column {} out of a grid {} wide", col, self.width);
+        &mut self.cells[row * self.width + col]
+    }
+}
+
+impl<T> Cells<T> for Grid<T> {
+    fn get(&self, position: (isize, isize)) -> Option<&T> {
+        Grid::get(self, position)
+    }
+
+    fn bounds(&self) -> Bounds {
+        Bounds { rows: 0..self.height as isize, cols: 0..self.width as isize }
+    }
+
+    fn count(&self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.cells.iter().filter(|&cell| cell == value).count()
+    }
+
+    /// Panics out of the grid, which does not grow.
+    fn set(&mut self, position: (isize, isize), value: T) {
+        let (height, width) = (self.height, self.width);
+        let inside = self
+            .contains(position)
+            .unwrap_or_else(|| panic!("position {:?} out of a grid of {}x{}", position, height, width));
+        self[inside] = value;
+    }
+}
+
+impl std::fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+/// How a view turns the grid it shows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transform {
+    Identity,
+    /// Turned a quarter clockwise.
+    Rotate90,
+    Rotate180,
+    /// Rows and columns swapped.
+    Transpose,
+}
+
+/// A grid seen turned or flipped.
+#[derive(Clone, Copy)]
+pub struct View<'g, T> {
+    grid: &'g Grid<T>,
+    transform: Transform,
+}
+
+impl<T> View<'_, T> {
+    pub fn height(&self) -> usize {
+        match self.transform {
+            Transform::Identity | Transform::Rotate180 => self.grid.height,
+            Transform::Rotate90 | Transform::Transpose => self.grid.width,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        match self.transform {
+            Transform::Identity | Transform::Rotate180 => self.grid.width,
+            Transform::Rotate90 | Transform::Transpose => self.grid.height,
+        }
+    }
+
+    /// The position in the grid of a position in the view.
+    pub fn to_grid(&self, (row, col): Position) -> Position {
+        let (height, width) = (self.grid.height, self.grid.width);
+        match self.transform {
+            Transform::Identity => (row, col),
+            Transform::Rotate90 => (height - 1 - col, row),
+            Transform::Rotate180 => (height - 1 - row, width - 1 - col),
+            Transform::Transpose => (col, row),
+        }
+    }
+}
+
+impl<T> Index<Position> for View<'_, T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        &self.grid[self.to_grid(position)]
+    }
+}
+
+/// A grid that grows to hold any position set, keeping its cells contiguous.
+/// It at least doubles when it grows, so setting cells takes amortised
+/// constant time.
+#[derive(Clone, Debug)]
+pub struct GrowingGrid<T> {
+    fill: T,
+    /// The position of the first of `cells`.
+    origin: (isize, isize),
+    cells: Grid<T>,
+    bounds: Bounds,
+}
+
+impl<T: Clone> GrowingGrid<T> {
+    /// An empty grid, whose cells are `fill` until set.
+    pub fn new(fill: T) -> Self {
+        Self { cells: Grid::new(0, 0, fill.clone()), fill, origin: (0, 0), bounds: Bounds::default() }
+    }
+
+    fn allocated(&self) -> Bounds {
+        Bounds {
+            rows: self.origin.0..self.origin.0 + self.cells.height as isize,
+            cols: self.origin.1..self.origin.1 + self.cells.width as isize,
+        }
+    }
+
+    fn offset(&self, (row, col): (isize, isize)) -> Position {
+        ((row - self.origin.0) as usize, (col - self.origin.1) as usize)
+    }
+
+    /// Make room for a position, at least doubling the size in its direction.
+    fn grow(&mut self, (row, col): (isize, isize)) {
+        let extend = |range: Range<isize>, at: isize| {
+            let length = range.len().max(1) as isize;
+            if range.is_empty() {
+                at..at + 1
+            } else if at < range.start {
+                at.min(range.start - length)..range.end
+            } else if at >= range.end {
+                range.start..(at + 1).max(range.end + length)
+            } else {
+                range
+            }
+        };
+        let allocated = self.allocated();
+        let (rows, cols) = (extend(allocated.rows, row), extend(allocated.cols, col));
+        let grown = Grid::new(rows.len(), cols.len(), self.fill.clone());
+        let previous = std::mem::replace(&mut self.cells, grown);
+        let origin = std::mem::replace(&mut self.origin, (rows.start, cols.start));
+        for ((row, col), cell) in previous.positions().zip(previous.cells) {
+            let offset = self.offset((origin.0 + row as isize, origin.1 + col as isize));
+            self.cells[offset] = cell;
+        }
+    }
+}
+
+impl<T: Clone> Cells<T> for GrowingGrid<T> {
+    fn get(&self, position: (isize, isize)) -> Option<&T> {
+        self.bounds.contains(position).then(|| &self.cells[self.offset(position)])
+    }
+
+    fn bounds(&self) -> Bounds {
+        self.bounds.clone()
+    }
+
+    fn count(&self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.bounds.positions().filter(|&position| self.get(position) == Some(value)).count()
+    }
+
+    fn set(&mut self, position: (isize, isize), value: T) {
+        if !self.allocated().contains(position) {
+            self.grow(position);
+        }
+        let offset = self.offset(position);
+        self.cells[offset] = value;
+        self.bounds = self.bounds.including(position);
+    }
+}
+
+/// A grid holding only the cells set, for positions far apart.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<(isize, isize), T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self { cells: HashMap::new() }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    /// Parse a character map, a line per row, keeping the cells `f` gives.
+    pub fn parse(input: &str, f: impl FnMut(char) -> Option<T>) -> Self {
+        let mut grid = Self::default();
+        grid.set_parsed(input, f);
+        grid
+    }
+
+    pub fn contains(&self, position: (isize, isize)) -> bool {
+        self.cells.contains_key(&position)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((isize, isize), &T)> {
+        self.cells.iter().map(|(&position, cell)| (position, cell))
+    }
+}
+
+impl<T> Cells<T> for SparseGrid<T> {
+    fn get(&self, position: (isize, isize)) -> Option<&T> {
+        self.cells.get(&position)
+    }
+
+    fn bounds(&self) -> Bounds {
+        self.cells.keys().fold(Bounds::default(), |bounds, &position| bounds.including(position))
+    }
+
+    fn count(&self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        self.cells.values().filter(|&cell| cell == value).count()
+    }
+
+    fn set(&mut self, position: (isize, isize), value: T) {
+        self.cells.insert(position, value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MAP: &str = "#.#\n..\n";
+
+    #[test]
+    fn parse_and_access() {
+        let mut grid = Grid::parse(MAP, |c| c);
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid.to_string(), "#.#\n.. \n");
+        assert_eq!(grid.get((0, 2)), Some(&'#'));
+        assert_eq!(grid.get((-1, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid[grid.wrap((-1, 3))], '.');
+        grid.set((1, 2), '#');
+        assert_eq!(grid.find(|&c| c == '#'), Some((0, 0)));
+        assert_eq!(grid.iter().filter(|(_, &c)| c == '#').count(), 3);
+        assert!(grid.map(|&c| c == '#')[(1, 2)]);
+
+        let digit = |c: char| c.to_digit(10).ok_or_else(|| AocError::new(format!("invalid digit {:?}", c)));
+        assert_eq!(Grid::try_parse("12\n34", digit).unwrap()[(1, 0)], 3);
+        assert_eq!(Grid::try_parse("12\n3x", digit).unwrap_err().line, Some(2));
+        assert_eq!(Grid::try_parse("12\n3", digit).unwrap_err().cause, "invalid digit ' '");
+    }
+
+    #[test]
+    fn rows_and_neighbours() {
+        let grid = Grid::parse("123\n456\n789", |c| c.to_digit(10).unwrap());
+        assert_eq!(grid.rows().map(|row| row.iter().sum::<u32>()).collect::<Vec<_>>(), vec![6, 15, 24]);
+        assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours4((2, 1)).map(|position| grid[position]).sum::<u32>(), 5 + 7 + 9);
+    }
+
+    #[test]
+    #[should_panic(expected = "position (2, 0) out of a grid of 2x3")]
+    fn set_out_of_bounds() {
+        Grid::parse(MAP, |c| c).set((2, 0), '#');
+    }
+
+    #[test]
+    #[should_panic(expected = "empty grid")]
+    fn wrap_empty() {
+        Grid::parse("", |c| c).wrap((1, 1));
+    }
+
+    #[test]
+    fn views() {
+        let grid = Grid::parse("123\n456", |c| c);
+        let read = |transform| {
+            let view = grid.view(transform);
+            (0..view.height())
+                .map(|row| (0..view.width()).map(|col| view[(row, col)]).collect::<String>())
+                .collect::<Vec<_>>()
+                .join("/")
+        };
+        assert_eq!(read(Transform::Identity), "123/456");
+        assert_eq!(read(Transform::Rotate90), "41/52/63");
+        assert_eq!(read(Transform::Rotate180), "654/321");
+        assert_eq!(read(Transform::Transpose), "14/25/36");
+    }
+
+    #[test]
+    fn growing() {
+        let mut grid = GrowingGrid::new('.');
+        assert_eq!(grid.bounds().area(), 0);
+        assert_eq!(grid.get((0, 0)), None);
+        grid.set((0, 0), '#');
+        grid.set((-3, 5), '#');
+        grid.set((2, -1), 'o');
+        assert_eq!(grid.bounds(), Bounds { rows: -3..3, cols: -1..6 });
+        assert_eq!((grid.get((0, 0)), grid.get((-3, 5)), grid.get((2, -1))), (Some(&'#'), Some(&'#'), Some(&'o')));
+        assert_eq!(grid.get((1, 1)), Some(&'.'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!((grid.count(&'#'), grid.count(&'.')), (2, 39));
+
+        for col in 0..1000 {
+            grid.set((0, -col), '#');
+        }
+        assert!(grid.cells.width < 4000);
+        assert_eq!(grid.count(&'#'), 1001);
+
+        let mut parsed = GrowingGrid::new(' ');
+        parsed.set_parsed("#.\n.#", |c| (c == '#').then_some(c));
+        assert_eq!((parsed.bounds().area(), parsed.count(&'#'), parsed.get((0, 1))), (4, 2, Some(&' ')));
+    }
+
+    #[test]
+    fn sparse() {
+        let mut grid = SparseGrid::parse("#.\n.#", |c| (c == '#').then_some(c));
+        grid.set((-1_000_000, 7), '@');
+        assert!(grid.contains((1, 1)) && !grid.contains((0, 1)));
+        assert_eq!(grid.get((-1_000_000, 7)), Some(&'@'));
+        assert_eq!(grid.bounds(), Bounds { rows: -1_000_000..2, cols: 0..8 });
+        assert_eq!((grid.len(), grid.count(&'#')), (3, 2));
+        assert_eq!(Grid::parse("#.\n.#", |c| c).bounds().area(), 4);
+    }
+}
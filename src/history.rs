@@ -0,0 +1,242 @@
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::bench::{format_duration, Measurement};
+use crate::input;
+
+/// The history file of the checkout, whatever the current directory.
+pub fn default_path() -> PathBuf {
+    input::source_root().join("bench_history.jsonl")
+}
+
+/// One benchmarked step, as stored in the JSON lines history file.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Record {
+    pub commit: String,
+    pub timestamp: u64,
+    pub year: u32,
+    pub day: u32,
+    pub step: String,
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub stddev_ns: u64,
+}
+
+impl Record {
+    fn key(&self) -> (u32, u32, &str) {
+        (self.year, self.day, &self.step)
+    }
+}
+
+/// The checked out commit, suffixed with `-dirty` when the tree has local changes.
+pub fn current_commit() -> String {
+    std::process::Command::new("git")
+        .current_dir(input::source_root())
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+        .unwrap_or_else(|| "unknown".to_owned())
+}
+
+pub fn append(path: &Path, measurements: &[Measurement]) -> std::io::Result<()> {
+    let commit = current_commit();
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    for measurement in measurements {
+        let record = Record {
+            commit: commit.clone(),
+            timestamp,
+            year: measurement.year,
+            day: measurement.day,
+            step: measurement.step.to_string(),
+            runs: measurement.stats.runs,
+            min_ns: measurement.stats.min.as_nanos() as u64,
+            median_ns: measurement.stats.median.as_nanos() as u64,
+            mean_ns: measurement.stats.mean.as_nanos() as u64,
+            stddev_ns: measurement.stats.stddev.as_nanos() as u64,
+        };
+        writeln!(file, "{}", serde_json::to_string(&record).unwrap())?;
+    }
+    Ok(())
+}
+
+pub fn load(path: &Path) -> std::io::Result<Vec<Record>> {
+    let file = std::fs::File::open(path)
+        .map_err(|error| std::io::Error::new(error.kind(), format!("{}: {}", path.display(), error)))?;
+    std::io::BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+        .map(|(index, line)| {
+            serde_json::from_str(&line?).map_err(|error| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{}:{}: {}", path.display(), index + 1, error),
+                )
+            })
+        })
+        .collect()
+}
+
+/// The latest record of each step measured at `commit`, in year/day order.
+/// `commit` may be abbreviated, unless it is recorded as is.
+fn latest_at<'r>(records: &'r [Record], commit: &str) -> Vec<&'r Record> {
+    let exact = records.iter().any(|record| record.commit == commit);
+    let matches = |record: &&Record| match exact {
+        true => record.commit == commit,
+        false => record.commit.starts_with(commit),
+    };
+
+    let mut latest: Vec<&Record> = Vec::new();
+    for record in records.iter().filter(matches) {
+        match latest.iter_mut().find(|other| other.key() == record.key()) {
+            Some(other) if other.timestamp <= record.timestamp => *other = record,
+            Some(_) => (),
+            None => latest.push(record),
+        }
+    }
+    latest.sort_by_key(|record| (record.year, record.day));
+    latest
+}
+
+/// Commits in the order they were last benchmarked, most recent first.
+fn commits(records: &[Record]) -> Vec<&str> {
+    let mut records = records.iter().collect::<Vec<_>>();
+    records.sort_by_key(|record| std::cmp::Reverse(record.timestamp));
+    let mut commits = Vec::new();
+    for record in records {
+        if !commits.contains(&record.commit.as_str()) {
+            commits.push(record.commit.as_str());
+        }
+    }
+    commits
+}
+
+pub struct Comparison<'r> {
+    pub baseline: &'r Record,
+    pub current: &'r Record,
+}
+
+impl Comparison<'_> {
+    /// Relative change of the median, in percent.
+    pub fn change(&self) -> f64 {
+        (self.current.median_ns as f64 / self.baseline.median_ns.max(1) as f64 - 1.0) * 100.0
+    }
+}
+
+/// Pair the steps measured at both commits. `current` defaults to the most
+/// recently benchmarked commit, and `baseline` to the one benchmarked before it.
+pub fn compare<'r>(
+    records: &'r [Record],
+    baseline: Option<&str>,
+    current: Option<&str>,
+) -> Result<Vec<Comparison<'r>>, String> {
+    let commits = commits(records);
+    let current = current
+        .or_else(|| commits.first().copied())
+        .ok_or("the history is empty")?;
+    let baseline = baseline
+        .or_else(|| commits.iter().copied().find(|commit| !commit.starts_with(current)))
+        .ok_or("no baseline commit in the history")?;
+
+    let baseline = latest_at(records, baseline);
+    let current = latest_at(records, current);
+    if baseline.is_empty() || current.is_empty() {
+        return Err("no record for the compared commits".to_owned());
+    }
+
+    Ok(current
+        .into_iter()
+        .filter_map(|current| {
+            baseline
+                .iter()
+                .find(|baseline| baseline.key() == current.key())
+                .map(|baseline| Comparison { baseline, current })
+        })
+        .collect())
+}
+
+const SEPARATOR: &str = "+------+----+-------+------------+------------+----------+";
+
+/// Print the comparisons and return whether any median regressed by more than `threshold` percent.
+pub fn print_comparisons(comparisons: &[Comparison], threshold: f64) -> bool {
+    if let Some(comparison) = comparisons.first() {
+        println!("Baseline {} -> current {}", comparison.baseline.commit, comparison.current.commit);
+    }
+    println!("{}", SEPARATOR);
+    println!("| Year | Day| Step  |  Baseline  |  Current   |  Change  |");
+    println!("{}", SEPARATOR);
+
+    let mut regressed = false;
+    for comparison in comparisons {
+        let change = comparison.change();
+        let flag = if change > threshold {
+            regressed = true;
+            " REGRESSION"
+        } else {
+            ""
+        };
+        println!(
+            "| {:04} | {:02} | {:<5} | {:>10} | {:>10} | {:>+7.1}% |{}",
+            comparison.current.year,
+            comparison.current.day,
+            comparison.current.step,
+            format_duration(Duration::from_nanos(comparison.baseline.median_ns)),
+            format_duration(Duration::from_nanos(comparison.current.median_ns)),
+            change,
+            flag,
+        );
+    }
+    println!("{}", SEPARATOR);
+
+    regressed
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn record(commit: &str, timestamp: u64, day: u32, median_ns: u64) -> Record {
+        Record {
+            commit: commit.to_owned(),
+            timestamp,
+            year: 2022,
+            day,
+            step: "p1".to_owned(),
+            runs: 10,
+            min_ns: median_ns,
+            median_ns,
+            mean_ns: median_ns,
+            stddev_ns: 0,
+        }
+    }
+
+    #[test]
+    fn compare_latest_commits() {
+        let records = vec![
+            record("aaa", 1, 1, 100),
+            record("aaa", 1, 2, 100),
+            record("bbb", 2, 1, 150),
+            record("bbb", 3, 1, 120),
+            record("bbb", 3, 2, 90),
+        ];
+        let comparisons = compare(&records, None, None).unwrap();
+        let changes = comparisons
+            .iter()
+            .map(|comparison| (comparison.current.day, comparison.change().round() as i64))
+            .collect::<Vec<_>>();
+        assert_eq!(changes, vec![(1, 20), (2, -10)]);
+
+        assert!(compare(&records, Some("ccc"), None).is_err());
+        assert_eq!(compare(&records, Some("bbb"), Some("aaa")).unwrap()[0].change().round(), -17.0);
+    }
+}
@@ -0,0 +1,199 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::config::Config;
+use crate::solution::{Part, Solution};
+
+/// Where the input of a puzzle is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// `rsc/yearYYYY/input/dayDD.txt`
+    Puzzle,
+    /// `rsc/yearYYYY/example/dayDD.txt`, or the `dayDD-1.txt`/`dayDD-2.txt` pair
+    /// when the parts have different examples.
+    Example,
+    /// One file for both parts, or one file per part. `-` reads stdin.
+    Files(Vec<PathBuf>),
+}
+
+/// How the whitespace of an input is normalised before a day sees it. Line
+/// endings always become `\n`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Whitespace {
+    /// Remove the whitespace at the end of the input, final newline included.
+    TrimEnd,
+    /// Keep it, for inputs laid out in columns.
+    Keep,
+}
+
+pub fn normalize(input: &str, whitespace: Whitespace) -> String {
+    let input = input.replace("\r\n", "\n");
+    match whitespace {
+        Whitespace::TrimEnd => input.trim_end().to_owned(),
+        Whitespace::Keep => input,
+    }
+}
+
+/// The checkout the binary was built from, holding `src`, `dev` and
+/// `aoc.toml`, so that it runs from anywhere. The current directory when the
+/// checkout is gone.
+pub fn source_root() -> &'static Path {
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+    if manifest.is_dir() {
+        manifest
+    } else {
+        Path::new(".")
+    }
+}
+
+/// The directory holding `rsc`: `AOC_DATA` or the `data` setting of
+/// `aoc.toml` when given, otherwise the source root.
+pub fn root() -> &'static Path {
+    static ROOT: OnceLock<PathBuf> = OnceLock::new();
+    ROOT.get_or_init(|| {
+        Config::load()
+            .ok()
+            .and_then(|config| config.data)
+            .unwrap_or_else(|| source_root().to_owned())
+    })
+}
+
+/// Where a path relative to the root, such as `rsc/year2022/answers.toml`, is.
+pub fn resolve(path: impl AsRef<Path>) -> PathBuf {
+    root().join(path)
+}
+
+/// The files of `rsc` included in the binary with the `embed` feature.
+#[cfg(feature = "embed")]
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/embedded.rs"));
+
+    pub fn get(path: &std::path::Path) -> Option<&'static str> {
+        let path = path.to_str()?.replace('\\', "/");
+        FILES.iter().find(|(name, _)| *name == path).map(|(_, content)| *content)
+    }
+}
+
+/// Read a file given relative to the root, naming it in the error. With the
+/// `embed` feature, files missing on disk are read from the binary.
+pub fn read(path: impl AsRef<Path>) -> std::io::Result<String> {
+    let resolved = resolve(&path);
+    match std::fs::read_to_string(&resolved) {
+        #[cfg(feature = "embed")]
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => match embedded::get(path.as_ref()) {
+            Some(content) => Ok(content.to_owned()),
+            None => Err(std::io::Error::new(error.kind(), format!("{}: {}", resolved.display(), error))),
+        },
+        Err(error) => Err(std::io::Error::new(error.kind(), format!("{}: {}", resolved.display(), error))),
+        Ok(content) => Ok(content),
+    }
+}
+
+/// Stdin can only be read once, so it is kept for every part and day using it.
+static STDIN: OnceLock<String> = OnceLock::new();
+
+/// Read a file given on the command line, naming it in the error.
+fn read_file(path: &PathBuf) -> std::io::Result<String> {
+    if path.as_os_str() == "-" {
+        if let Some(input) = STDIN.get() {
+            return Ok(input.clone());
+        }
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        Ok(STDIN.get_or_init(|| input).clone())
+    } else {
+        std::fs::read_to_string(path)
+            .map_err(|error| std::io::Error::new(error.kind(), format!("{}: {}", path.display(), error)))
+    }
+}
+
+/// Where the puzzle input of a day is kept.
+pub fn puzzle_path(year: u32, day: u32) -> PathBuf {
+    PathBuf::from(format!("rsc/year{}/input/day{:02}.txt", year, day))
+}
+
+/// Where the example of a day is kept, or the example of one part when the
+/// parts have different ones.
+pub fn example_path(year: u32, day: u32, part: Option<Part>) -> PathBuf {
+    let suffix = match part {
+        None => "",
+        Some(Part::One) => "-1",
+        Some(Part::Two) => "-2",
+    };
+    PathBuf::from(format!("rsc/year{}/example/day{:02}{}.txt", year, day, suffix))
+}
+
+/// Load the inputs of a puzzle for its tests, or `None` after saying why
+/// when they are not available, such as encrypted inputs without their key.
+/// The reason goes to stderr itself rather than through `eprintln!`, which
+/// the test harness captures: a skipped test shows without `--nocapture`.
+#[cfg(test)]
+pub fn load_or_skip(solution: &Solution, source: &Source) -> Option<(String, String)> {
+    use std::io::Write;
+
+    match load(solution, source) {
+        Ok(inputs) => Some(inputs),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            let _ = writeln!(std::io::stderr(), "SKIPPED {}.{:02}: {}", solution.year, solution.day, error);
+            None
+        }
+        Err(error) => panic!("{}", error),
+    }
+}
+
+/// Load the inputs of the two parts of a puzzle, normalised as the day asks.
+pub fn load(solution: &Solution, source: &Source) -> std::io::Result<(String, String)> {
+    let (input1, input2) = load_raw(solution.year, solution.day, source)?;
+    Ok((normalize(&input1, solution.whitespace), normalize(&input2, solution.whitespace)))
+}
+
+fn load_raw(year: u32, day: u32, source: &Source) -> std::io::Result<(String, String)> {
+    match source {
+        Source::Puzzle => {
+            let storage = crate::storage::get().map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidInput, error))?;
+            let input = storage.read(year, day)?;
+            Ok((input.clone(), input))
+        }
+        Source::Example => match read(example_path(year, day, None)) {
+            Ok(input) => Ok((input.clone(), input)),
+            Err(_) => {
+                let input1 = read(example_path(year, day, Some(Part::One)))?;
+                let input2 = read(example_path(year, day, Some(Part::Two)))?;
+                Ok((input1, input2))
+            }
+        },
+        Source::Files(paths) => match paths.as_slice() {
+            [path] => {
+                let input = read_file(path)?;
+                Ok((input.clone(), input))
+            }
+            [path1, path2] => Ok((read_file(path1)?, read_file(path2)?)),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "expected one input file, or one per part",
+            )),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalize_whitespace() {
+        assert_eq!(normalize(">><\r\n", Whitespace::TrimEnd), ">><");
+        assert_eq!(normalize("    [D]    \r\n[N] [C]    \r\n\r\n", Whitespace::Keep), "    [D]    \n[N] [C]    \n\n");
+        assert_eq!(normalize("1\n\n2  \n\n", Whitespace::TrimEnd), "1\n\n2");
+    }
+
+    #[test]
+    fn read_from_root() {
+        // The data root of aoc.toml or AOC_DATA, the crate otherwise
+        let data = Config::load().ok().and_then(|config| config.data);
+        assert_eq!(root(), data.as_deref().unwrap_or(Path::new(env!("CARGO_MANIFEST_DIR"))));
+        assert!(read(example_path(2022, 1, None)).is_ok());
+        assert!(read("rsc/year2022/input/day26.txt").unwrap_err().to_string().contains("day26.txt: "));
+    }
+}
@@ -1,6 +1,41 @@
-mod year2021;
-mod year2022;
-mod year2023;
+mod answers;
+mod bench;
+#[cfg(test)]
+mod cases;
+mod client;
+mod config;
+mod error;
+mod example;
+mod geometry;
+mod graph;
+mod grid;
+mod history;
+mod input;
+mod report;
+mod runner;
+mod scaffold;
+mod search;
+mod selector;
+mod solution;
+mod status;
+mod storage;
+mod submit;
+mod watch;
+
+use input::Source;
+use selector::{Selected, Selector};
+use solution::Solution;
+
+/// Declare the year modules and collect their solutions into `YEARS`.
+macro_rules! years {
+    ($($year:ident),* $(,)?) => {
+        $(mod $year;)*
+
+        const YEARS: &[&[Solution]] = &[$($year::SOLUTIONS,)*];
+    };
+}
+
+years!(year2021, year2022, year2023);
 
 #[cfg(test)]
 fn get_stem_name(path: &std::path::Path) -> String {
@@ -12,19 +47,33 @@ fn get_stem_name(path: &std::path::Path) -> String {
     .to_owned()
 }
 
+/// Look up the registered solution of the day file at `path`.
+#[cfg(test)]
+fn get_solution(path: &std::path::Path) -> &'static Solution {
+    let day = get_stem_name(path);
+    let year = get_stem_name(path.parent().unwrap());
+    let day = day.trim_start_matches("day").parse().unwrap();
+    let year = year.trim_start_matches("year").parse().unwrap();
+    solution::find(year, day).unwrap()
+}
+
 #[macro_export]
 macro_rules! test_aoc {
-    ($star1:expr, $star2:expr) => {
+    () => {
         #[cfg(test)]
         mod test {
             #[test]
             fn run() {
-                let current_file_path = std::path::PathBuf::from(file!());
-                let day = crate::get_stem_name(&current_file_path);
-                let year = crate::get_stem_name(&current_file_path.parent().unwrap());
+                let solution = $crate::get_solution(std::path::Path::new(file!()));
+                let expected = $crate::answers::expected(solution.year, solution.day).unwrap();
 
-                let input = std::fs::read_to_string(format!("rsc/{}/input/{}.txt", year, day)).unwrap();
-                assert_eq!(super::run(&input, &input), ($star1.to_string(), $star2.to_string()));
+                let Some((input1, input2)) = $crate::input::load_or_skip(solution, &$crate::input::Source::Puzzle) else { return };
+                for (part, input) in [($crate::solution::Part::One, &input1), ($crate::solution::Part::Two, &input2)] {
+                    if let Some(expected) = expected.get(part) {
+                        let answer = solution.run_part(part, input, &$crate::solution::PuzzleParams::default()).unwrap_or_else(|error| panic!("{}", error));
+                        assert_eq!(answer, expected, "{}", part);
+                    }
+                }
             }
         }
     };
@@ -37,140 +86,526 @@ macro_rules! test_example_aoc {
         mod example {
             #[test]
             fn run() {
-                let current_file_path = std::path::PathBuf::from(file!());
-                let day = crate::get_stem_name(&current_file_path);
-                let year = crate::get_stem_name(&current_file_path.parent().unwrap());
-
-                let path = format!("rsc/{}/example/{}.txt", year, day);
-                let (input1, input2) = match std::fs::read_to_string(&path) {
-                    Ok(input) => (input.clone(), input),
-                    Err(_) => {
-                        let input1 = std::fs::read_to_string(format!("rsc/{}/example/{}-1.txt", year, day)).unwrap();
-                        let input2 = std::fs::read_to_string(format!("rsc/{}/example/{}-2.txt", year, day)).unwrap();
-                        (input1, input2)
-                    }
-                };
-                assert_eq!(super::run(&input1, &input2), ($star1.to_string(), $star2.to_string()));
+                let solution = $crate::get_solution(std::path::Path::new(file!()));
+
+                let Some((input1, input2)) = $crate::input::load_or_skip(solution, &$crate::input::Source::Example) else { return };
+                let answers = solution.run(&input1, &input2, &$crate::solution::PuzzleParams::example()).unwrap_or_else(|error| panic!("{}", error));
+                assert_eq!(answers, ($star1.to_string(), $star2.to_string()));
             }
         }
     };
 }
 
+/// Generate a test running every case of
+/// `rsc/yearYYYY/example/dayDD.examples.toml`, checking the answers they list:
+/// a case added to the file is tested without touching the day.
 #[macro_export]
-macro_rules! run {
+macro_rules! test_examples_aoc {
     () => {
-        pub fn run(input1: &str, input2: &str) -> (String, String) {
-            (part1(&input1), part2(&input2))
+        #[cfg(test)]
+        mod examples {
+            #[test]
+            fn run() {
+                let solution = $crate::get_solution(std::path::Path::new(file!()));
+                $crate::cases::check(solution);
+            }
         }
     };
 }
 
-
+/// Expose the `part1`/`part2` functions of a day, with an optional puzzle
+/// title and the parsing function shared by both parts. The parts return
+/// either a `String` or a `Result<String, AocError>`; they and the parsing
+/// function take the `PuzzleParams` as a second argument when they need them.
+/// The inputs have their trailing whitespace removed, unless a `Whitespace`
+/// variant follows a semicolon, as in `crate::run!("Title"; Keep)`.
 #[macro_export]
-macro_rules! run_challenge {
-    ($year:expr) => {
-        pub fn run_challenges() {
-            for day in 1..26 {
-                run_challenge(day)
-            }
-        }
-
-        pub fn run_challenge(day: u32) {
-            let input = std::fs::read_to_string(format!("rsc/year{}/input/day{:02}.txt", $year, day)).unwrap();
-        
-            let start = std::time::Instant::now();
-        
-            let (star1, star2) = match day {
-                1 => day01::run(&input, &input),
-                2 => day02::run(&input, &input),
-                3 => day03::run(&input, &input),
-                4 => day04::run(&input, &input),
-                5 => day05::run(&input, &input),
-                6 => day06::run(&input, &input),
-                7 => day07::run(&input, &input),
-                8 => day08::run(&input, &input),
-                9 => day09::run(&input, &input),
-                10 => day10::run(&input, &input),
-                11 => day11::run(&input, &input),
-                12 => day12::run(&input, &input),
-                13 => day13::run(&input, &input),
-                14 => day14::run(&input, &input),
-                15 => day15::run(&input, &input),
-                16 => day16::run(&input, &input),
-                17 => day17::run(&input, &input),
-                18 => day18::run(&input, &input),
-                19 => day19::run(&input, &input),
-                20 => day20::run(&input, &input),
-                21 => day21::run(&input, &input),
-                22 => day22::run(&input, &input),
-                23 => day23::run(&input, &input),
-                24 => day24::run(&input, &input),
-                25 => day25::run(&input, &input),
-                _ => panic!("Unknown day"),
-            };
-        
-            let elapsed_time = start.elapsed();
-        
-            println!(
-                "| {:04} | {:02} | {:>20} | {:>20} | {:>8} ms|",
-                $year,
+macro_rules! run {
+    () => {
+        $crate::run!(@ None, None, TrimEnd);
+    };
+    ($title:literal $(; $whitespace:ident)?) => {
+        $crate::run!(@ Some($title), None, $($whitespace)? TrimEnd);
+    };
+    ($title:literal, $parse:path $(; $whitespace:ident)?) => {
+        $crate::run!(@ Some($title), Some(|input, params| {
+            let _ = std::hint::black_box($crate::solution::WithParams::call_with(&$parse, input, params));
+        }), $($whitespace)? TrimEnd);
+    };
+    (@ $title:expr, $parse:expr, $whitespace:ident $($default:ident)?) => {
+        pub const fn solution(year: u32, day: u32) -> $crate::solution::Solution {
+            $crate::solution::Solution {
+                year,
                 day,
-                star1,
-                star2,
-                elapsed_time.as_millis()
-            );
+                part1: |input, params| {
+                    $crate::error::Answer::into_answer($crate::solution::WithParams::call_with(&part1, input, params))
+                },
+                part2: |input, params| {
+                    $crate::error::Answer::into_answer($crate::solution::WithParams::call_with(&part2, input, params))
+                },
+                parse: $parse,
+                title: $title,
+                whitespace: $crate::input::Whitespace::$whitespace,
+            }
         }
-        
     };
 }
 
 #[derive(argh::FromArgs)]
 #[argh(description = "Advent Of Code")]
 struct Args {
-    #[argh(positional, default = "String::new()")]
-    #[argh(description = "year or year.day")]
-    test_filter: String,
+    #[argh(subcommand)]
+    command: Option<Command>,
+
+    #[argh(positional)]
+    #[argh(description = "puzzles to run: 2022, 2021..2023, 2022.01-05, 2022.03,07,12 or 2022.16.p2")]
+    selectors: Vec<Selector>,
+
+    #[argh(switch)]
+    #[argh(description = "list the registered solutions instead of running them")]
+    list: bool,
+
+    #[argh(option)]
+    #[argh(description = "read the input from this file instead, '-' for stdin; give it twice for separate part 1 and part 2 inputs")]
+    input: Vec<std::path::PathBuf>,
+
+    #[argh(switch)]
+    #[argh(description = "run on the example inputs")]
+    example: bool,
+
+    #[argh(option, default = "report::Format::Table")]
+    #[argh(description = "output format: table (default), json, csv or markdown")]
+    format: report::Format,
+
+    #[argh(option, default = "1")]
+    #[argh(description = "number of parts run in parallel (default 1)")]
+    jobs: usize,
+
+    #[argh(option, default = "60")]
+    #[argh(description = "seconds after which a part is reported as timed out, 0 for no limit (default 60)")]
+    timeout: u64,
+
+    #[argh(option)]
+    #[argh(description = "puzzle parameter overriding the one of the input, such as y=10")]
+    param: Vec<String>,
+}
+
+#[derive(argh::FromArgs)]
+#[argh(subcommand)]
+enum Command {
+    Bench(BenchArgs),
+    Compare(CompareArgs),
+    Verify(VerifyArgs),
+    Scaffold(ScaffoldArgs),
+    Fetch(FetchArgs),
+    Submit(SubmitArgs),
+    Example(ExampleArgs),
+    Watch(WatchArgs),
+    Status(StatusArgs),
+    Encrypt(EncryptArgs),
+}
+
+#[derive(argh::FromArgs)]
+#[argh(subcommand, name = "bench")]
+#[argh(description = "time parsing and each part over repeated runs")]
+struct BenchArgs {
+    #[argh(positional)]
+    #[argh(description = "puzzles to benchmark, with the same syntax as for running")]
+    selectors: Vec<Selector>,
+
+    #[argh(option)]
+    #[argh(description = "benchmark on this input file instead, '-' for stdin")]
+    input: Vec<std::path::PathBuf>,
+
+    #[argh(switch)]
+    #[argh(description = "benchmark on the example inputs")]
+    example: bool,
+
+    #[argh(option)]
+    #[argh(description = "puzzle parameter overriding the one of the input, such as y=10")]
+    param: Vec<String>,
+
+    #[argh(option, default = "3")]
+    #[argh(description = "unmeasured runs before measuring (default 3)")]
+    warmup: u32,
+
+    #[argh(option)]
+    #[argh(description = "exact number of measured runs per step")]
+    runs: Option<u32>,
+
+    #[argh(option, default = "1000")]
+    #[argh(description = "time budget in ms per step when --runs is not given (default 1000)")]
+    budget: u64,
+
+    #[argh(switch)]
+    #[argh(description = "append the results to the history file")]
+    save: bool,
+
+    #[argh(option, default = "history::default_path()")]
+    #[argh(description = "history file (default bench_history.jsonl of the checkout)")]
+    history: std::path::PathBuf,
+}
+
+#[derive(argh::FromArgs)]
+#[argh(subcommand, name = "compare")]
+#[argh(description = "compare saved benchmarks of two commits and flag regressions")]
+struct CompareArgs {
+    #[argh(option)]
+    #[argh(description = "commit to compare against (default: the one benchmarked before --current)")]
+    baseline: Option<String>,
+
+    #[argh(option)]
+    #[argh(description = "commit to check (default: the last benchmarked one)")]
+    current: Option<String>,
+
+    #[argh(option, default = "10.0")]
+    #[argh(description = "median slowdown in percent flagged as a regression (default 10)")]
+    threshold: f64,
+
+    #[argh(option, default = "history::default_path()")]
+    #[argh(description = "history file (default bench_history.jsonl of the checkout)")]
+    history: std::path::PathBuf,
+}
+
+#[derive(argh::FromArgs)]
+#[argh(subcommand, name = "verify")]
+#[argh(description = "run on the puzzle inputs and exit with 1 if any answer differs from rsc/yearYYYY/answers.toml")]
+struct VerifyArgs {
+    #[argh(positional)]
+    #[argh(description = "puzzles to verify, with the same syntax as for running")]
+    selectors: Vec<Selector>,
+
+    #[argh(option, default = "report::Format::Table")]
+    #[argh(description = "output format: table (default), json, csv or markdown")]
+    format: report::Format,
+
+    #[argh(option, default = "1")]
+    #[argh(description = "number of parts run in parallel (default 1)")]
+    jobs: usize,
+
+    #[argh(option, default = "60")]
+    #[argh(description = "seconds after which a part is reported as timed out, 0 for no limit (default 60)")]
+    timeout: u64,
+}
+
+#[derive(argh::FromArgs)]
+#[argh(subcommand, name = "scaffold")]
+#[argh(description = "create the stub days and resources of a year from dev/template and register it; existing files are kept")]
+struct ScaffoldArgs {
+    #[argh(positional)]
+    #[argh(description = "year to scaffold")]
+    year: u32,
+}
+
+#[derive(argh::FromArgs)]
+#[argh(subcommand, name = "fetch")]
+#[argh(description = "download the puzzle inputs missing in rsc/yearYYYY/input, using the session token of AOC_SESSION or aoc.toml")]
+struct FetchArgs {
+    #[argh(positional)]
+    #[argh(description = "puzzles to fetch, with the same syntax as for running")]
+    selectors: Vec<Selector>,
+}
+
+#[derive(argh::FromArgs)]
+#[argh(subcommand, name = "submit")]
+#[argh(description = "run a part on the puzzle input and submit its answer, recording it in rsc/yearYYYY/answers.toml when right")]
+struct SubmitArgs {
+    #[argh(positional)]
+    #[argh(description = "the part to submit, such as 2023.05.p1")]
+    selector: Selector,
+}
+
+#[derive(argh::FromArgs)]
+#[argh(subcommand, name = "example")]
+#[argh(description = "extract the examples of a puzzle and their answers into rsc/yearYYYY/example")]
+struct ExampleArgs {
+    #[argh(positional)]
+    #[argh(description = "the day, such as 2023.05")]
+    selector: Selector,
+
+    #[argh(option)]
+    #[argh(description = "saved puzzle page to read instead of downloading it")]
+    page: Option<std::path::PathBuf>,
+}
+
+#[derive(argh::FromArgs)]
+#[argh(subcommand, name = "watch")]
+#[argh(description = "rerun the example tests then the puzzle inputs of days whenever their source or resources change")]
+struct WatchArgs {
+    #[argh(positional)]
+    #[argh(description = "puzzles to watch, with the same syntax as for running")]
+    selectors: Vec<Selector>,
+
+    #[argh(option, default = "500")]
+    #[argh(description = "milliseconds between two checks for changes (default 500)")]
+    interval: u64,
+}
+
+#[derive(argh::FromArgs)]
+#[argh(subcommand, name = "status")]
+#[argh(description = "show the stars of each day, its stubbed parts and the example tests to fix")]
+struct StatusArgs {
+    #[argh(positional)]
+    #[argh(description = "puzzles to show, with the same syntax as for running")]
+    selectors: Vec<Selector>,
+
+    #[argh(switch)]
+    #[argh(description = "also run the parts that are not stubs, marking those failing on the puzzle input")]
+    run: bool,
+}
+
+#[derive(argh::FromArgs)]
+#[argh(subcommand, name = "encrypt")]
+#[argh(description = "encrypt the plain puzzle inputs with the key of AOC_INPUT_KEY or aoc.toml, next to them as dayDD.txt.enc")]
+struct EncryptArgs {
+    #[argh(positional)]
+    #[argh(description = "puzzles whose input to encrypt, with the same syntax as for running")]
+    selectors: Vec<Selector>,
+}
+
+/// Report an error that prevents running anything, and exit.
+fn fail(message: impl std::fmt::Display) -> ! {
+    eprintln!("Error: {}", message);
+    std::process::exit(2);
+}
+
+fn select(selectors: &[Selector]) -> Vec<Selected> {
+    selector::select(selectors).unwrap_or_else(|error| fail(format!("invalid selector: {}", error)))
+}
+
+fn source(input: Vec<std::path::PathBuf>, example: bool) -> Source {
+    match (input.is_empty(), example) {
+        (true, false) => Source::Puzzle,
+        (true, true) => Source::Example,
+        (false, false) => Source::Files(input),
+        (false, true) => fail("--input and --example cannot be used together"),
+    }
+}
+
+/// The parameters of the parts run on `source`, with the `name=value` overrides.
+fn params(source: &Source, overrides: &[String]) -> solution::PuzzleParams {
+    let overrides = overrides
+        .iter()
+        .map(|param| match param.split_once('=') {
+            Some((name, value)) if !name.is_empty() => (name.to_owned(), value.to_owned()),
+            _ => fail(format!("invalid parameter '{}', expected name=value", param)),
+        })
+        .collect();
+    solution::PuzzleParams { example: matches!(source, Source::Example), overrides }
+}
+
+fn run(
+    selected: &[Selected],
+    source: &Source,
+    params: solution::PuzzleParams,
+    format: report::Format,
+    jobs: usize,
+    timeout: u64,
+) -> Vec<runner::Row> {
+    let options = runner::Options {
+        jobs,
+        timeout: Some(std::time::Duration::from_secs(timeout)).filter(|timeout| !timeout.is_zero()),
+        params,
+    };
+    let mut report = report::Report::begin(format);
+    let start = std::time::Instant::now();
+
+    let mut rows = Vec::new();
+    runner::run(selected, source, &options, |row| {
+        report.row(&row);
+        rows.push(row);
+    });
+
+    report.end(start.elapsed());
+    rows
 }
 
 fn main() {
     let args: Args = argh::from_env();
-    let day_regex = regex::Regex::new(r"^(?P<y>\d{4}).(?P<d>\d{2})$").unwrap();
-    let year_regex = regex::Regex::new(r"^(?P<y>\d{4})$").unwrap();
 
-    println!("            +----------------------+----------------------+------------+");
-    println!("            |        Star 1        |        Star 2        |    Time    |");
-    println!("+------+----+----------------------+----------------------+------------+");
-    let start = std::time::Instant::now();
-    
-    if args.test_filter.len() == 0 {
-        year2022::run_challenges();
-        year2023::run_challenges();
-    }
-    else if let Some(cap) = year_regex.captures(&args.test_filter) {
-        let year = cap.name("y").unwrap().as_str().parse::<u32>().unwrap();
-        match year {
-            2021 => year2021::run_challenges(),
-            2022 => year2022::run_challenges(),
-            2023 => year2023::run_challenges(),
-            _ => panic!("Invalid year"),
+    match args.command {
+        Some(Command::Bench(bench)) => {
+            let source = source(bench.input, bench.example);
+            let options = bench::Options {
+                warmup: bench.warmup,
+                runs: bench.runs,
+                budget: std::time::Duration::from_millis(bench.budget),
+                params: params(&source, &bench.param),
+            };
+            let measurements = bench::run(&select(&bench.selectors), &source, &options);
+            if bench.save {
+                history::append(&bench.history, &measurements).unwrap_or_else(|error| fail(error));
+            }
+            return;
         }
-    }
-    else if let Some(cap) = day_regex.captures(&args.test_filter) {
-        let year = cap.name("y").unwrap().as_str().parse::<u32>().unwrap();
-        let day = cap.name("d").unwrap().as_str().parse::<u32>().unwrap();
-        match year {
-            2021 => year2021::run_challenge(day),
-            2022 => year2022::run_challenge(day),
-            2023 => year2023::run_challenge(day),
-            _ => panic!("Invalid year"),
+        Some(Command::Compare(compare)) => {
+            let records = history::load(&compare.history).unwrap_or_else(|error| fail(error));
+            let comparisons = history::compare(&records, compare.baseline.as_deref(), compare.current.as_deref())
+                .unwrap_or_else(|error| fail(format!("cannot compare: {}", error)));
+            if history::print_comparisons(&comparisons, compare.threshold) {
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Verify(verify)) => {
+            let rows = run(&select(&verify.selectors), &Source::Puzzle, Default::default(), verify.format, verify.jobs, verify.timeout);
+            if rows.iter().flat_map(|row| row.parts()).any(|part| matches!(part.check, answers::Check::Wrong | answers::Check::Error)) {
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Scaffold(scaffold)) => {
+            if !(2015..=9999).contains(&scaffold.year) {
+                fail(format!("invalid year {}", scaffold.year));
+            }
+            let created = scaffold::year(input::source_root(), input::root(), scaffold.year).unwrap_or_else(|error| fail(error));
+            if created.is_empty() {
+                println!("year{} is already scaffolded", scaffold.year);
+            }
+            for path in created {
+                println!("wrote {}", path.display());
+            }
+            return;
         }
+        Some(Command::Fetch(fetch)) => {
+            let selected = select(&fetch.selectors);
+            let config = config::Config::load().unwrap_or_else(|error| fail(error));
+            let mut client = client::Client::new(&config).unwrap_or_else(|error| fail(error));
+            let storage = storage::get().unwrap_or_else(|error| fail(error));
+            let mut failed = false;
+            for Selected { solution, .. } in selected {
+                match client::fetch(&mut client, storage, solution.year, solution.day) {
+                    Ok(client::Fetched::Cached(path)) => println!("{} is already there", path.display()),
+                    Ok(client::Fetched::Downloaded(path)) => println!("wrote {}", path.display()),
+                    Err(error) => {
+                        eprintln!("Error: {}", error);
+                        failed = true;
+                    }
+                }
+            }
+            if failed {
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Submit(submit)) => {
+            let selected = select(std::slice::from_ref(&submit.selector));
+            let (solution, part) = match selected.as_slice() {
+                [Selected { solution, parts }] if parts.len() == 1 => (solution, parts[0]),
+                _ => fail(format!("{} is not a single part, such as 2023.05.p1", submit.selector)),
+            };
+            let (year, day) = (solution.year, solution.day);
+            let expected = answers::expected(year, day).unwrap_or_else(|error| fail(error));
+            if let Some(answer) = expected.get(part) {
+                fail(format!("{} is already solved with {}", submit.selector, answer));
+            }
+
+            let (input1, input2) = input::load(solution, &Source::Puzzle).unwrap_or_else(|error| fail(error));
+            let input = match part {
+                solution::Part::One => input1,
+                solution::Part::Two => input2,
+            };
+            let answer = solution.run_part(part, &input, &Default::default()).unwrap_or_else(|error| fail(error));
+            println!("Submitting {} for {}", answer, submit.selector);
+
+            let config = config::Config::load().unwrap_or_else(|error| fail(error));
+            let mut client = client::Client::new(&config).unwrap_or_else(|error| fail(error));
+            let response = submit::submit(&mut client, &input::resolve(submit::guesses_path(year)), year, day, part, &answer)
+                .unwrap_or_else(|error| fail(error));
+            match response.wait {
+                Some(wait) => println!("{} (wait {}s before the next answer)", response.verdict, wait.as_secs()),
+                None => println!("{}", response.verdict),
+            }
+            if response.verdict != submit::Verdict::Correct {
+                std::process::exit(1);
+            }
+            answers::record(year, day, part, &answer).unwrap_or_else(|error| fail(error));
+            println!("recorded in {}", input::resolve(answers::path(year)).display());
+            return;
+        }
+        Some(Command::Example(example)) => {
+            let selected = select(std::slice::from_ref(&example.selector));
+            let [Selected { solution, .. }] = selected.as_slice() else {
+                fail(format!("{} is not a single day, such as 2023.05", example.selector));
+            };
+            let page = match &example.page {
+                Some(path) => std::fs::read_to_string(path)
+                    .unwrap_or_else(|error| fail(format!("{}: {}", path.display(), error))),
+                None => {
+                    let config = config::Config::load().unwrap_or_else(|error| fail(error));
+                    let mut client = client::Client::new(&config).unwrap_or_else(|error| fail(error));
+                    client.puzzle(solution.year, solution.day).unwrap_or_else(|error| fail(error))
+                }
+            };
+            let examples = example::extract(&page);
+            example::save(input::source_root(), input::root(), solution.year, solution.day, &examples)
+                .unwrap_or_else(|error| fail(error));
+            return;
+        }
+        Some(Command::Watch(watch)) => {
+            let selected = select(&watch.selectors);
+            let selectors = watch.selectors.iter().map(ToString::to_string).collect::<Vec<_>>();
+            watch::watch(&selectors, &selected, std::time::Duration::from_millis(watch.interval));
+        }
+        Some(Command::Status(status)) => {
+            let selected = select(&status.selectors);
+            let mut statuses = status::collect(&selected).unwrap_or_else(|error| fail(error));
+            if status.run {
+                let selected = selected
+                    .iter()
+                    .zip(&statuses)
+                    .map(|(Selected { solution, parts }, status)| Selected {
+                        solution,
+                        parts: parts.iter().copied().filter(|&part| status.state(part) != status::State::Stub).collect(),
+                    })
+                    .filter(|selected| !selected.parts.is_empty())
+                    .collect::<Vec<_>>();
+                let options = runner::Options {
+                    jobs: std::thread::available_parallelism().map_or(1, usize::from),
+                    timeout: Some(std::time::Duration::from_secs(60)),
+                    params: Default::default(),
+                };
+                let mut rows = Vec::new();
+                runner::run(&selected, &Source::Puzzle, &options, |row| rows.push(row));
+                status::check(&mut statuses, &rows);
+            }
+            println!("{}", status::format(&statuses));
+            return;
+        }
+        Some(Command::Encrypt(encrypt)) => {
+            let storage = storage::get().unwrap_or_else(|error| fail(error));
+            let mut failed = false;
+            for Selected { solution, .. } in select(&encrypt.selectors) {
+                let plain = storage.plain_path(solution.year, solution.day);
+                if !std::fs::metadata(plain).is_ok_and(|metadata| metadata.len() > 0) {
+                    continue;
+                }
+                match storage.encrypt(solution.year, solution.day) {
+                    Ok(path) => println!("wrote {}", path.display()),
+                    Err(error) => {
+                        eprintln!("Error: {}", error);
+                        failed = true;
+                    }
+                }
+            }
+            if failed {
+                std::process::exit(1);
+            }
+            return;
+        }
+        None => (),
     }
-    else {
-        panic!("Invalid test filter");
+
+    let selected = select(&args.selectors);
+    let source = source(args.input, args.example);
+
+    if args.list {
+        for Selected { solution, .. } in selected {
+            println!("{:04}.{:02} {}", solution.year, solution.day, solution.title.unwrap_or(""));
+        }
+        return;
     }
 
-    let elapsed_time = start.elapsed();
-    println!("+------+----+----------------------+----------------------+------------+");
-    println!("+      |    |                      |                      | {:>8} ms|", elapsed_time.as_millis());
-    println!("+------+----+----------------------+----------------------+------------+");
+    let params = params(&source, &args.param);
+    run(&selected, &source, params, args.format, args.jobs, args.timeout);
 }
@@ -0,0 +1,226 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::bench::format_duration;
+use crate::error::AocError;
+use crate::runner::{Outcome, PartResult, Row};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "markdown" => Ok(Format::Markdown),
+            _ => Err(format!("unknown format '{}', expected table, json, csv or markdown", s)),
+        }
+    }
+}
+
+const TABLE_WIDTH: usize = 20;
+const TABLE_SEPARATOR: &str = "+------+----+------------------------+------------------------+------------+------------+";
+
+/// Split an answer into lines of at most `TABLE_WIDTH` characters, so that
+/// long or multi-line answers keep the table aligned.
+fn wrap(answer: &str) -> Vec<String> {
+    answer
+        .lines()
+        .flat_map(|line| {
+            let chars = line.chars().collect::<Vec<_>>();
+            match chars.is_empty() {
+                true => vec![String::new()],
+                false => chars.chunks(TABLE_WIDTH).map(|chunk| chunk.iter().collect()).collect(),
+            }
+        })
+        .collect()
+}
+
+/// The answer of a part, or the message of its error.
+fn answer(part: &Option<PartResult>) -> String {
+    match part.as_ref().map(|part| &part.outcome) {
+        Some(Outcome::Answer(answer)) => answer.clone(),
+        Some(outcome) => format!("{} {}", outcome.label(), outcome.error().unwrap()),
+        None => String::new(),
+    }
+}
+
+/// The answer of a part as shown in the table, where errors are listed below it.
+fn table_answer(part: &Option<PartResult>) -> String {
+    match part.as_ref().map(|part| &part.outcome) {
+        Some(outcome) if outcome.error().is_some() => outcome.label().to_owned(),
+        _ => answer(part),
+    }
+}
+
+fn symbol(part: &Option<PartResult>) -> &'static str {
+    part.as_ref().map_or(" ", |part| part.check.symbol())
+}
+
+fn check(part: &Option<PartResult>) -> &'static str {
+    part.as_ref().map_or("", |part| part.check.name())
+}
+
+fn nanos(part: &Option<PartResult>) -> String {
+    part.as_ref().map_or(String::new(), |part| part.time.as_nanos().to_string())
+}
+
+fn cpu_nanos(part: &Option<PartResult>) -> String {
+    part.as_ref()
+        .and_then(|part| part.cpu_time)
+        .map_or(String::new(), |time| time.as_nanos().to_string())
+}
+
+fn format_cpu_time(time: Option<Duration>) -> String {
+    time.map_or("-".to_owned(), format_duration)
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn markdown_cell(field: &str) -> String {
+    field.trim_end().replace('|', "\\|").replace('\n', "<br>")
+}
+
+/// Writes results to stdout as they come, in one of the supported formats.
+pub struct Report {
+    format: Format,
+    rows: usize,
+    cpu_time: Option<Duration>,
+    errors: Vec<(&'static str, AocError)>,
+}
+
+impl Report {
+    pub fn begin(format: Format) -> Self {
+        match format {
+            Format::Table => {
+                println!("            +------------------------+------------------------+------------+------------+");
+                println!("            |         Star 1         |         Star 2         |    Time    |    CPU     |");
+                println!("{}", TABLE_SEPARATOR);
+            }
+            Format::Json => print!("["),
+            Format::Csv => println!(
+                "year,day,title,part1,part1_check,part1_ns,part1_cpu_ns,part2,part2_check,part2_ns,part2_cpu_ns"
+            ),
+            Format::Markdown => {
+                println!("| Year | Day | Title | Star 1 | Star 2 | Time |");
+                println!("|-----:|----:|-------|-------:|-------:|-----:|");
+            }
+        }
+        Self { format, rows: 0, cpu_time: Some(Duration::ZERO), errors: Vec::new() }
+    }
+
+    pub fn row(&mut self, row: &Row) {
+        match self.format {
+            Format::Table => {
+                let (star1, star2) = (wrap(&table_answer(&row.part1)), wrap(&table_answer(&row.part2)));
+                for line in 0..star1.len().max(star2.len()).max(1) {
+                    let star1 = star1.get(line).map_or("", String::as_str);
+                    let star2 = star2.get(line).map_or("", String::as_str);
+                    if line == 0 {
+                        println!(
+                            "| {:04} | {:02} | {:>20} {} | {:>20} {} | {:>10} | {:>10} |",
+                            row.year,
+                            row.day,
+                            star1,
+                            symbol(&row.part1),
+                            star2,
+                            symbol(&row.part2),
+                            format_duration(row.time()),
+                            format_cpu_time(row.cpu_time())
+                        );
+                    } else {
+                        println!("|      |    | {:>20}   | {:>20}   |            |            |", star1, star2);
+                    }
+                }
+            }
+            Format::Json => {
+                let separator = if self.rows == 0 { "" } else { "," };
+                print!("{}\n  {}", separator, serde_json::to_string(row).unwrap());
+            }
+            Format::Csv => println!(
+                "{},{},{},{},{},{},{},{},{},{},{}",
+                row.year,
+                row.day,
+                csv_field(row.title.unwrap_or("")),
+                csv_field(&answer(&row.part1)),
+                check(&row.part1),
+                nanos(&row.part1),
+                cpu_nanos(&row.part1),
+                csv_field(&answer(&row.part2)),
+                check(&row.part2),
+                nanos(&row.part2),
+                cpu_nanos(&row.part2)
+            ),
+            Format::Markdown => println!(
+                "| {} | {:02} | {} | {} | {} | {} |",
+                row.year,
+                row.day,
+                markdown_cell(row.title.unwrap_or("")),
+                markdown_cell(&format!("{} {}", answer(&row.part1), symbol(&row.part1))),
+                markdown_cell(&format!("{} {}", answer(&row.part2), symbol(&row.part2))),
+                format_duration(row.time())
+            ),
+        }
+        self.rows += 1;
+        self.errors.extend(
+            row.parts()
+                .filter_map(|part| Some((part.outcome.label(), part.outcome.error()?.clone()))),
+        );
+        self.cpu_time = self.cpu_time.zip(row.cpu_time()).map(|(total, time)| total + time);
+    }
+
+    /// Close the report, with the wall time of the whole run.
+    pub fn end(self, total: Duration) {
+        match self.format {
+            Format::Table => {
+                println!("{}", TABLE_SEPARATOR);
+                println!(
+                    "+      |    |                        |                        | {:>10} | {:>10} |",
+                    format_duration(total),
+                    format_cpu_time(self.cpu_time)
+                );
+                println!("{}", TABLE_SEPARATOR);
+                for (label, error) in self.errors {
+                    println!("{} {}", label, error);
+                }
+            }
+            Format::Json => println!("{}]", if self.rows == 0 { "" } else { "\n" }),
+            Format::Csv | Format::Markdown => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn wrap_long_answers() {
+        assert_eq!(wrap("13060"), vec!["13060"]);
+        assert_eq!(wrap("20=02=120-=-2110-0=1-"), vec!["20=02=120-=-2110-0=1", "-"]);
+        assert_eq!(wrap("#..#\n.##."), vec!["#..#", ".##."]);
+        assert!(wrap("").is_empty());
+    }
+
+    #[test]
+    fn escape_fields() {
+        assert_eq!(csv_field("FJUBULRZ"), "FJUBULRZ");
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+        assert_eq!(markdown_cell("a|b\nc\n"), "a\\|b<br>c");
+    }
+}
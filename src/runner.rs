@@ -0,0 +1,347 @@
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Once};
+use std::time::{Duration, Instant};
+
+use crate::answers::{self, Check, Expected};
+use crate::error::AocError;
+use crate::input::{self, Source};
+use crate::selector::Selected;
+use crate::solution::{Part, PuzzleParams, Solution};
+
+pub struct Options {
+    /// Number of parts run in parallel.
+    pub jobs: usize,
+    /// Wall-clock time after which a part is given up on.
+    pub timeout: Option<Duration>,
+    pub params: PuzzleParams,
+}
+
+/// What running a part gave.
+#[derive(Clone, Debug, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Answer(String),
+    Error(#[serde(serialize_with = "serialize_display")] AocError),
+    Panic(#[serde(serialize_with = "serialize_display")] AocError),
+    Timeout(#[serde(serialize_with = "serialize_display")] AocError),
+}
+
+impl Outcome {
+    pub fn error(&self) -> Option<&AocError> {
+        match self {
+            Outcome::Answer(_) => None,
+            Outcome::Error(error) | Outcome::Panic(error) | Outcome::Timeout(error) => Some(error),
+        }
+    }
+
+    /// Short name of the failure, shown in place of the answer.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Outcome::Answer(_) => "",
+            Outcome::Error(_) => "ERROR",
+            Outcome::Panic(_) => "PANIC",
+            Outcome::Timeout(_) => "TIMEOUT",
+        }
+    }
+}
+
+fn serialize_display<S: serde::Serializer>(value: &impl std::fmt::Display, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/// The outcome of one part and the time it took.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct PartResult {
+    #[serde(flatten)]
+    pub outcome: Outcome,
+    pub check: Check,
+    #[serde(rename = "time_ns", serialize_with = "serialize_nanos")]
+    pub time: Duration,
+    /// CPU time of the thread that ran the part, where the platform reports it.
+    #[serde(rename = "cpu_ns", serialize_with = "serialize_optional_nanos")]
+    pub cpu_time: Option<Duration>,
+}
+
+fn serialize_nanos<S: serde::Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_nanos() as u64)
+}
+
+fn serialize_optional_nanos<S: serde::Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => serialize_nanos(duration, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// The outcome of running the selected parts of a day.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct Row {
+    pub year: u32,
+    pub day: u32,
+    pub title: Option<&'static str>,
+    pub part1: Option<PartResult>,
+    pub part2: Option<PartResult>,
+}
+
+impl Row {
+    pub fn parts(&self) -> impl Iterator<Item = &PartResult> {
+        [&self.part1, &self.part2].into_iter().flatten()
+    }
+
+    pub fn time(&self) -> Duration {
+        self.parts().map(|part| part.time).sum()
+    }
+
+    pub fn cpu_time(&self) -> Option<Duration> {
+        self.parts().map(|part| part.cpu_time).sum()
+    }
+}
+
+/// CPU time consumed by the calling thread so far.
+#[cfg(unix)]
+fn thread_cpu_time() -> Option<Duration> {
+    let mut time = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    // SAFETY: `time` is a valid timespec for clock_gettime to write to.
+    match unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, &mut time) } {
+        0 => Some(Duration::new(time.tv_sec as u64, time.tv_nsec as u32)),
+        _ => None,
+    }
+}
+
+#[cfg(not(unix))]
+fn thread_cpu_time() -> Option<Duration> {
+    None
+}
+
+thread_local! {
+    /// Whether the thread runs a part in `catch_panic`, whose panics are reported in the results.
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Keep the panics of isolated parts off stderr, recording where they
+/// happened instead. Other threads keep the default hook.
+fn install_panic_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if ISOLATED.with(Cell::get) {
+                PANIC_LOCATION.with(|location| *location.borrow_mut() = info.location().map(ToString::to_string));
+            } else {
+                default(info);
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_owned());
+    match PANIC_LOCATION.with(|location| location.borrow_mut().take()) {
+        Some(location) => format!("panicked at {}: {}", location, message),
+        None => format!("panicked: {}", message),
+    }
+}
+
+/// Run `f`, turning a panic into an error located at `part` of `solution`,
+/// without it being printed.
+pub fn catch_panic<T>(solution: &Solution, part: Option<Part>, f: impl FnOnce() -> T) -> Result<T, AocError> {
+    install_panic_hook();
+    let previous = ISOLATED.with(|isolated| isolated.replace(true));
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+    ISOLATED.with(|isolated| isolated.set(previous));
+    result.map_err(|payload| AocError::new(panic_message(&*payload)).located(solution.year, solution.day, part))
+}
+
+/// Run a part on a thread of its own, so that a panic only fails that part,
+/// and a part still running after `timeout` is reported as such. Such a part
+/// cannot be stopped: it is left running in the background until the
+/// program exits.
+fn run_isolated(
+    solution: &'static Solution,
+    part: Part,
+    input: Arc<str>,
+    params: PuzzleParams,
+    timeout: Option<Duration>,
+) -> (Result<String, Outcome>, Duration, Option<Duration>) {
+    install_panic_hook();
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let cpu_start = thread_cpu_time();
+        let start = Instant::now();
+        let answer = catch_panic(solution, Some(part), || solution.run_part(part, &input, &params));
+        let time = start.elapsed();
+        let cpu_time = cpu_start.zip(thread_cpu_time()).map(|(start, end)| end - start);
+
+        let answer = match answer {
+            Ok(Ok(answer)) => Ok(answer),
+            Ok(Err(error)) => Err(Outcome::Error(error)),
+            Err(error) => Err(Outcome::Panic(error)),
+        };
+        // The receiver is gone when the part timed out.
+        let _ = sender.send((answer, time, cpu_time));
+    });
+
+    let received = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout),
+        None => receiver.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+    };
+    received.unwrap_or_else(|_| {
+        let timeout = timeout.unwrap_or_default();
+        let error = AocError::new(format!("timed out after {}", crate::bench::format_duration(timeout)));
+        (Err(Outcome::Timeout(error.located(solution.year, solution.day, Some(part)))), timeout, None)
+    })
+}
+
+type Inputs = (Arc<str>, Arc<str>);
+
+/// Load the inputs of a day, and its expected answers when running on the
+/// puzzle input.
+fn prepare(Selected { solution, .. }: &Selected, source: &Source) -> Result<(Inputs, Expected), AocError> {
+    let located = |error: String| AocError::new(error).located(solution.year, solution.day, None);
+    let (input1, input2) =
+        input::load(solution, source).map_err(|error| located(error.to_string()))?;
+    let inputs = (Arc::from(input1), Arc::from(input2));
+    let expected = match source {
+        Source::Puzzle => answers::expected(solution.year, solution.day).map_err(located)?,
+        _ => Expected::default(),
+    };
+    Ok((inputs, expected))
+}
+
+/// Pass on the complete rows that follow the ones already passed.
+fn emit_ready(rows: &[Row], remaining: &[usize], next_row: &mut usize, on_row: &mut impl FnMut(Row)) {
+    while *next_row < rows.len() && remaining[*next_row] == 0 {
+        on_row(rows[*next_row].clone());
+        *next_row += 1;
+    }
+}
+
+/// Run the selected parts of each day on `jobs` threads, every part being its
+/// own task. Rows are passed to `on_row` in the order of `selected`, as soon
+/// as they and all the rows before them are complete. Answers are only
+/// checked against the recorded ones when running on the puzzle input.
+/// A day that fails, panics, times out, or whose input cannot be loaded,
+/// gets the failure in its cells and does not stop the others.
+pub fn run(selected: &[Selected], source: &Source, options: &Options, mut on_row: impl FnMut(Row)) {
+    let prepared = selected.iter().map(|selected| prepare(selected, source)).collect::<Vec<_>>();
+    let tasks = selected
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| prepared[*index].is_ok())
+        .flat_map(|(index, Selected { parts, .. })| parts.iter().map(move |&part| (index, part)))
+        .collect::<Vec<_>>();
+
+    let mut rows = selected
+        .iter()
+        .map(|Selected { solution, .. }| Row {
+            year: solution.year,
+            day: solution.day,
+            title: solution.title,
+            part1: None,
+            part2: None,
+        })
+        .collect::<Vec<_>>();
+    let mut remaining = selected.iter().map(|selected| selected.parts.len()).collect::<Vec<_>>();
+    for (index, Selected { parts, .. }) in selected.iter().enumerate() {
+        if let Err(error) = &prepared[index] {
+            for &part in parts {
+                let result = PartResult {
+                    outcome: Outcome::Error(error.clone()),
+                    check: Check::Error,
+                    time: Duration::ZERO,
+                    cpu_time: None,
+                };
+                match part {
+                    Part::One => rows[index].part1 = Some(result),
+                    Part::Two => rows[index].part2 = Some(result),
+                }
+            }
+            remaining[index] = 0;
+        }
+    }
+    let mut next_row = 0;
+    emit_ready(&rows, &remaining, &mut next_row, &mut on_row);
+
+    let next_task = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    std::thread::scope(|scope| {
+        for _ in 0..options.jobs.clamp(1, tasks.len().max(1)) {
+            let (tasks, prepared, next_task) = (&tasks, &prepared, &next_task);
+            let sender = sender.clone();
+            scope.spawn(move || {
+                while let Some(&(index, part)) = tasks.get(next_task.fetch_add(1, Ordering::Relaxed)) {
+                    let Ok((inputs, expected)) = &prepared[index] else { continue };
+                    let input = match part {
+                        Part::One => inputs.0.clone(),
+                        Part::Two => inputs.1.clone(),
+                    };
+
+                    let (answer, time, cpu_time) =
+                        run_isolated(selected[index].solution, part, input, options.params.clone(), options.timeout);
+                    let (outcome, check) = match answer {
+                        Ok(answer) => {
+                            let check = Check::new(expected.get(part), &answer);
+                            (Outcome::Answer(answer), check)
+                        }
+                        Err(outcome) => (outcome, Check::Error),
+                    };
+                    sender.send((index, part, PartResult { outcome, check, time, cpu_time })).unwrap();
+                }
+            });
+        }
+        drop(sender);
+
+        for (index, part, result) in receiver {
+            match part {
+                Part::One => rows[index].part1 = Some(result),
+                Part::Two => rows[index].part2 = Some(result),
+            }
+            remaining[index] -= 1;
+            emit_ready(&rows, &remaining, &mut next_row, &mut on_row);
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::input::Whitespace;
+
+    fn panics(_: &str, _: &PuzzleParams) -> Result<String, AocError> {
+        panic!("Pattern not found")
+    }
+
+    fn sleeps(_: &str, _: &PuzzleParams) -> Result<String, AocError> {
+        std::thread::sleep(Duration::from_secs(1));
+        Ok(String::new())
+    }
+
+    static SOLUTION: Solution = Solution { year: 2022, day: 17, part1: panics, part2: sleeps, parse: None, title: None, whitespace: Whitespace::TrimEnd };
+
+    #[test]
+    fn isolate_parts() {
+        let (answer, ..) = run_isolated(&SOLUTION, Part::One, Arc::from(""), PuzzleParams::default(), None);
+        let Err(Outcome::Panic(error)) = answer else { panic!("expected a panic") };
+        assert_eq!(error.part, Some(Part::One));
+        assert!(error.cause.starts_with("panicked at src/runner.rs:"));
+        assert!(error.cause.ends_with(": Pattern not found"));
+
+        let (answer, time, _) = run_isolated(&SOLUTION, Part::Two, Arc::from(""), PuzzleParams::default(), Some(Duration::from_millis(10)));
+        let Err(Outcome::Timeout(error)) = answer else { panic!("expected a timeout") };
+        assert_eq!(error.to_string(), "2022.17.p2: timed out after 10.00 ms");
+        assert_eq!(time, Duration::from_millis(10));
+
+        let error = catch_panic(&SOLUTION, None, || panics("", &PuzzleParams::default())).unwrap_err();
+        assert_eq!((error.part, error.cause.ends_with(": Pattern not found")), (None, true));
+        assert_eq!(catch_panic(&SOLUTION, None, || 2 + 2).unwrap(), 4);
+    }
+}
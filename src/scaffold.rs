@@ -0,0 +1,109 @@
+use std::path::{Path, PathBuf};
+
+/// Placeholder for the year in the template files and directory names.
+const PLACEHOLDER: &str = "20xx";
+
+/// Copy the files of `from` that are missing in `to`, replacing the year
+/// placeholder in their content. Existing files, solved or not, are kept.
+fn copy_missing(from: &Path, to: &Path, year: u32, created: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    let mut entries = std::fs::read_dir(from)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_missing(&entry.path(), &target, year, created)?;
+        } else if !target.exists() {
+            let content = std::fs::read_to_string(entry.path())?;
+            std::fs::write(&target, content.replace(PLACEHOLDER, &year.to_string()))?;
+            created.push(target);
+        }
+    }
+    Ok(())
+}
+
+/// Add the year to the `years!` line of `main.rs`, keeping it sorted.
+/// Returns `None` when it is already there.
+fn register(main: &str, year: u32) -> Result<Option<String>, String> {
+    let module = format!("year{}", year);
+    let line = main
+        .lines()
+        .find(|line| line.starts_with("years!("))
+        .ok_or("no years! line in main.rs")?;
+    let mut years = line
+        .trim_start_matches("years!(")
+        .trim_end_matches(");")
+        .split(',')
+        .map(str::trim)
+        .filter(|year| !year.is_empty())
+        .collect::<Vec<_>>();
+    if years.contains(&module.as_str()) {
+        return Ok(None);
+    }
+    years.push(&module);
+    years.sort();
+    Ok(Some(main.replacen(line, &format!("years!({});", years.join(", ")), 1)))
+}
+
+/// Create the sources of a year from `dev/template` under `source`, its
+/// resources under `data`, and register it in `src/main.rs`. Returns the
+/// files changed.
+pub fn year(source: &Path, data: &Path, year: u32) -> Result<Vec<PathBuf>, String> {
+    let template = source.join("dev/template");
+    let mut created = Vec::new();
+    let copy = |from: &str, to: PathBuf, created: &mut Vec<PathBuf>| {
+        copy_missing(&template.join(from), &to, year, created)
+            .map_err(|error| format!("cannot copy dev/template/{}: {}", from, error))
+    };
+    copy("year20xx", source.join(format!("src/year{}", year)), &mut created)?;
+    copy("rsc", data.join(format!("rsc/year{}", year)), &mut created)?;
+
+    let path = source.join("src/main.rs");
+    let main = std::fs::read_to_string(&path).map_err(|error| format!("{}: {}", path.display(), error))?;
+    if let Some(main) = register(&main, year)? {
+        std::fs::write(&path, main).map_err(|error| format!("{}: {}", path.display(), error))?;
+        created.push(path);
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn register_years() {
+        let main = "mod answers;\n\nyears!(year2021, year2023);\n";
+        assert_eq!(register(main, 2022).unwrap().unwrap(), "mod answers;\n\nyears!(year2021, year2022, year2023);\n");
+        assert_eq!(register(main, 2023).unwrap(), None);
+        assert!(register("mod answers;\n", 2022).is_err());
+    }
+
+    #[test]
+    fn scaffold_twice() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write("dev/template/year20xx/mod.rs", "crate::solutions!(20xx; day01);\n");
+        write("dev/template/year20xx/day01.rs", "crate::run!();\n");
+        write("dev/template/rsc/input/day01.txt", "");
+        write("src/main.rs", "years!(year2022);\n");
+
+        let data = root.join("data");
+        let created = year(&root, &data, 2024).unwrap();
+        assert_eq!(created.len(), 4);
+        assert!(data.join("rsc/year2024/input/day01.txt").is_file());
+        assert!(!root.join("rsc").exists());
+        assert_eq!(std::fs::read_to_string(root.join("src/year2024/mod.rs")).unwrap(), "crate::solutions!(2024; day01);\n");
+        assert_eq!(std::fs::read_to_string(root.join("src/main.rs")).unwrap(), "years!(year2022, year2024);\n");
+
+        write("src/year2024/day01.rs", "solved");
+        assert!(year(&root, &data, 2024).unwrap().is_empty());
+        assert_eq!(std::fs::read_to_string(root.join("src/year2024/day01.rs")).unwrap(), "solved");
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,157 @@
+//! Searches of graphs given by a closure listing the successors of a node,
+//! from any number of start nodes to the first node matching a goal.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Breadth first search, every step costing 1: the distance from the nearest
+/// start node of each node reached, and the first goal reached.
+fn breadth_first<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (HashMap<N, usize>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        if goal(&node) {
+            return (distances, Some(node));
+        }
+        let distance = distances[&node] + 1;
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance);
+                queue.push_back(next);
+            }
+        }
+    }
+    (distances, None)
+}
+
+/// The distance to the nearest goal from the start nodes, every step
+/// costing 1.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (distances, goal) = breadth_first(starts, successors, goal);
+    goal.map(|goal| distances[&goal])
+}
+
+/// Best first search, with the nodes ordered by their cost plus their
+/// `heuristic`, and the cost of each node reached. The first goal reached is
+/// the cheapest when the heuristic never overestimates the cost to a goal, and
+/// drops by no more than the cost of a step.
+fn best_first<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (HashMap<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs = HashMap::new();
+    // Nodes need not be ordered: the heap holds their index in `nodes`.
+    let mut nodes = Vec::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        costs.insert(start.clone(), C::default());
+        heap.push(Reverse((heuristic(&start), C::default(), nodes.len())));
+        nodes.push(start);
+    }
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let node = nodes[index].clone();
+        if costs[&node] < cost {
+            continue;
+        }
+        if goal(&node) {
+            return (costs, Some(node));
+        }
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|&known| next_cost < known) {
+                costs.insert(next.clone(), next_cost);
+                heap.push(Reverse((next_cost + heuristic(&next), next_cost, nodes.len())));
+                nodes.push(next);
+            }
+        }
+    }
+    (costs, None)
+}
+
+/// The cost of every node reachable from the start nodes.
+pub fn dijkstra_all<N, C, I>(starts: impl IntoIterator<Item = N>, successors: impl FnMut(&N) -> I) -> HashMap<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(starts, successors, |_| C::default(), |_| false).0
+}
+
+/// The cost of the cheapest goal from the start nodes, exploring first the
+/// nodes the `heuristic` estimates closer to a goal. The heuristic must never
+/// overestimate, and drop by no more than the cost of a step.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (costs, goal) = best_first(starts, successors, heuristic, goal);
+    goal.map(|goal| costs[&goal])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Moves on a line: one forward, or two for a cost of 3.
+    fn steps(&n: &i32) -> [(i32, u32); 2] {
+        [(n + 1, 1), (n + 2, 3)]
+    }
+
+    #[test]
+    fn unweighted() {
+        let successors = |&n: &i32| [n + 1, n * 2].into_iter().filter(|&n| n <= 100);
+        assert_eq!(bfs([1], successors, |&n| n == 10), Some(4));
+        assert_eq!(bfs([3, 9], successors, |&n| n == 10), Some(1));
+        assert!(bfs([1], successors, |&n| n > 100).is_none());
+    }
+
+    #[test]
+    fn weighted() {
+        assert_eq!(astar([0], steps, |&n| (5 - n).max(0) as u32, |&n| n == 5), Some(5));
+        assert_eq!(astar([0], |&n| [(n + 1, 4), (n + 2, 3)], |_| 0, |&n| n >= 5), Some(9));
+
+        let costs = dijkstra_all([0], |&n| steps(&n).into_iter().filter(|&(n, _)| n <= 4));
+        assert_eq!(costs, HashMap::from([(0, 0), (1, 1), (2, 2), (3, 3), (4, 4)]));
+    }
+}
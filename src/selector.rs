@@ -0,0 +1,191 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::solution::{self, Part, Solution};
+
+lazy_static::lazy_static! {
+    static ref SELECTOR_REGEX: regex::Regex = regex::Regex::new(
+        r"^(?P<y1>\d{4})(?:\.\.(?P<y2>\d{4}))?(?:\.(?P<d>[\d,-]+))?(?:\.p(?P<p>[12]))?$"
+    ).unwrap();
+}
+
+/// Selects puzzles from the command line, e.g. `2022`, `2021..2023`,
+/// `2022.01-05`, `2022.03,07,12` or `2022.16.p2`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selector {
+    years: RangeInclusive<u32>,
+    days: Option<Vec<RangeInclusive<u32>>>,
+    part: Option<Part>,
+}
+
+/// A solution picked by the selectors, with the parts to run.
+pub struct Selected {
+    pub solution: &'static Solution,
+    pub parts: Vec<Part>,
+}
+
+impl Selector {
+    fn matches(&self, solution: &Solution) -> bool {
+        self.years.contains(&solution.year)
+            && self
+                .days
+                .as_ref()
+                .is_none_or(|days| days.iter().any(|days| days.contains(&solution.day)))
+    }
+
+    fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => vec![Part::One, Part::Two],
+        }
+    }
+}
+
+impl std::fmt::Display for Selector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.years.start())?;
+        if self.years.end() != self.years.start() {
+            write!(f, "..{}", self.years.end())?;
+        }
+        if let Some(days) = &self.days {
+            let days = days
+                .iter()
+                .map(|days| match days.start() == days.end() {
+                    true => format!("{:02}", days.start()),
+                    false => format!("{:02}-{:02}", days.start(), days.end()),
+                })
+                .collect::<Vec<_>>();
+            write!(f, ".{}", days.join(","))?;
+        }
+        if let Some(part) = self.part {
+            write!(f, ".{}", part)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_day(day: &str) -> Result<u32, String> {
+    match day.parse::<u32>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("invalid day '{}'", day)),
+    }
+}
+
+impl FromStr for Selector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cap = SELECTOR_REGEX
+            .captures(s)
+            .ok_or_else(|| format!("invalid selector '{}'", s))?;
+
+        let first_year = cap["y1"].parse::<u32>().unwrap();
+        let last_year = cap.name("y2").map_or(first_year, |year| year.as_str().parse::<u32>().unwrap());
+        if last_year < first_year {
+            return Err(format!("empty year range in '{}'", s));
+        }
+
+        let days = match cap.name("d") {
+            Some(days) => Some(
+                days.as_str()
+                    .split(',')
+                    .map(|days| match days.split_once('-') {
+                        Some((first, last)) => {
+                            let (first, last) = (parse_day(first)?, parse_day(last)?);
+                            if last < first {
+                                return Err(format!("empty day range '{}'", days));
+                            }
+                            Ok(first..=last)
+                        }
+                        None => parse_day(days).map(|day| day..=day),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            None => None,
+        };
+
+        let part = cap.name("p").map(|part| match part.as_str() {
+            "1" => Part::One,
+            _ => Part::Two,
+        });
+
+        Ok(Self { years: first_year..=last_year, days, part })
+    }
+}
+
+/// Resolve the selectors against the registry, keeping the registry order.
+/// No selector at all selects every part of every registered solution, so
+/// the 2021 year is run along with the others.
+pub fn select(selectors: &[Selector]) -> Result<Vec<Selected>, String> {
+    if let Some(selector) = selectors
+        .iter()
+        .find(|selector| !solution::all().any(|solution| selector.matches(solution)))
+    {
+        return Err(format!("no solution matches {}", selector));
+    }
+
+    Ok(solution::all()
+        .filter_map(|solution| {
+            let mut parts = if selectors.is_empty() {
+                vec![Part::One, Part::Two]
+            } else {
+                selectors
+                    .iter()
+                    .filter(|selector| selector.matches(solution))
+                    .flat_map(|selector| selector.parts())
+                    .collect::<Vec<_>>()
+            };
+            parts.sort_by_key(|part| *part == Part::Two);
+            parts.dedup();
+            (!parts.is_empty()).then_some(Selected { solution, parts })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let selector = "2022".parse::<Selector>().unwrap();
+        assert_eq!(selector, Selector { years: 2022..=2022, days: None, part: None });
+
+        let selector = "2021..2023".parse::<Selector>().unwrap();
+        assert_eq!(selector, Selector { years: 2021..=2023, days: None, part: None });
+
+        let selector = "2022.01-05".parse::<Selector>().unwrap();
+        assert_eq!(selector, Selector { years: 2022..=2022, days: Some(vec![1..=5]), part: None });
+
+        let selector = "2022.03,07,12".parse::<Selector>().unwrap();
+        assert_eq!(selector.days, Some(vec![3..=3, 7..=7, 12..=12]));
+
+        let selector = "2022.16.p2".parse::<Selector>().unwrap();
+        assert_eq!(selector, Selector { years: 2022..=2022, days: Some(vec![16..=16]), part: Some(Part::Two) });
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!("22".parse::<Selector>().is_err());
+        assert!("2023..2021".parse::<Selector>().is_err());
+        assert!("2022.26".parse::<Selector>().is_err());
+        assert!("2022.05-01".parse::<Selector>().is_err());
+        assert!("2022.01.p3".parse::<Selector>().is_err());
+    }
+
+    #[test]
+    fn display() {
+        for selector in ["2022", "2021..2023", "2022.01-05", "2022.03,07,12", "2022.16.p2"] {
+            assert_eq!(selector.parse::<Selector>().unwrap().to_string(), selector);
+        }
+    }
+
+    #[test]
+    fn select_merges_parts() {
+        let selectors = ["2022.01.p2", "2022.01-02.p1"].map(|s| s.parse::<Selector>().unwrap());
+        let selected = select(&selectors).unwrap();
+        assert_eq!(selected.len(), 2);
+        assert_eq!((selected[0].solution.day, selected[0].parts.clone()), (1, vec![Part::One, Part::Two]));
+        assert_eq!((selected[1].solution.day, selected[1].parts.clone()), (2, vec![Part::One]));
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::error::AocError;
+use crate::input::Whitespace;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => f.pad("p1"),
+            Part::Two => f.pad("p2"),
+        }
+    }
+}
+
+/// What a part is told about the input it runs on: whether it is an example,
+/// and the parameters given on the command line or by an example case.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzleParams {
+    pub example: bool,
+    pub overrides: BTreeMap<String, String>,
+}
+
+impl PuzzleParams {
+    #[cfg(test)]
+    pub fn example() -> Self {
+        Self { example: true, overrides: BTreeMap::new() }
+    }
+
+    /// The value of the parameter `name`: its override when given, otherwise
+    /// `example` or `real` depending on the input.
+    pub fn get<T: FromStr>(&self, name: &str, example: T, real: T) -> Result<T, AocError> {
+        match self.overrides.get(name) {
+            Some(value) => value.parse().map_err(|_| AocError::new(format!("invalid parameter {}={}", name, value))),
+            None if self.example => Ok(example),
+            None => Ok(real),
+        }
+    }
+}
+
+/// A function of the input, taking the puzzle parameters or not. `M` only
+/// tells the two apart, so that `run!` accepts both.
+pub trait WithParams<M, T> {
+    fn call_with(&self, input: &str, params: &PuzzleParams) -> T;
+}
+
+impl<F: Fn(&str) -> T, T> WithParams<fn(&str) -> T, T> for F {
+    fn call_with(&self, input: &str, _: &PuzzleParams) -> T {
+        self(input)
+    }
+}
+
+impl<F: Fn(&str, &PuzzleParams) -> T, T> WithParams<fn(&str, &PuzzleParams) -> T, T> for F {
+    fn call_with(&self, input: &str, params: &PuzzleParams) -> T {
+        self(input, params)
+    }
+}
+
+/// A solved (or stubbed) puzzle, as registered by its year module.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub year: u32,
+    pub day: u32,
+    pub part1: fn(&str, &PuzzleParams) -> Result<String, AocError>,
+    pub part2: fn(&str, &PuzzleParams) -> Result<String, AocError>,
+    /// The parsing shared by both parts, when the day registers it, so it
+    /// can be benchmarked on its own.
+    pub parse: Option<fn(&str, &PuzzleParams)>,
+    pub title: Option<&'static str>,
+    /// How the inputs are normalised before the parts see them.
+    pub whitespace: Whitespace,
+}
+
+impl Solution {
+    #[cfg(test)]
+    pub fn run(&self, input1: &str, input2: &str, params: &PuzzleParams) -> Result<(String, String), AocError> {
+        Ok((self.run_part(Part::One, input1, params)?, self.run_part(Part::Two, input2, params)?))
+    }
+
+    pub fn run_part(&self, part: Part, input: &str, params: &PuzzleParams) -> Result<String, AocError> {
+        let answer = match part {
+            Part::One => (self.part1)(input, params),
+            Part::Two => (self.part2)(input, params),
+        };
+        answer.map_err(|error| error.located(self.year, self.day, Some(part)))
+    }
+}
+
+/// Extract the day number from a `dayNN` module name.
+pub const fn day_from_module(name: &str) -> u32 {
+    let bytes = name.as_bytes();
+    assert!(bytes.len() == 5, "day modules must be named dayNN");
+    (bytes[3] - b'0') as u32 * 10 + (bytes[4] - b'0') as u32
+}
+
+/// All registered solutions, ordered by year then day.
+pub fn all() -> impl Iterator<Item = &'static Solution> {
+    crate::YEARS.iter().flat_map(|solutions| solutions.iter())
+}
+
+#[cfg(test)]
+pub fn find(year: u32, day: u32) -> Option<&'static Solution> {
+    all().find(|solution| solution.year == year && solution.day == day)
+}
+
+/// Register the given day modules of a year, exposing them as `SOLUTIONS`.
+#[macro_export]
+macro_rules! solutions {
+    ($year:literal; $($day:ident),* $(,)?) => {
+        pub const SOLUTIONS: &[$crate::solution::Solution] = &[
+            $($day::solution($year, $crate::solution::day_from_module(stringify!($day))),)*
+        ];
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn params() {
+        let mut params = PuzzleParams::example();
+        assert_eq!(params.get("y", 10, 2_000_000), Ok(10));
+        assert_eq!(PuzzleParams::default().get("y", 10, 2_000_000), Ok(2_000_000));
+        params.overrides.insert("y".to_owned(), "12".to_owned());
+        assert_eq!(params.get("y", 10, 2_000_000), Ok(12));
+        assert_eq!(params.get("y", true, false).unwrap_err().to_string(), "invalid parameter y=12");
+    }
+}
@@ -0,0 +1,210 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::answers::{self, Check, Expected};
+use crate::input;
+use crate::runner::Row;
+use crate::selector::Selected;
+use crate::solution::Part;
+
+/// Where a part stands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum State {
+    /// Still the `"0".to_string()` body of the template.
+    Stub,
+    /// Implemented, but without a recorded answer.
+    Unsolved,
+    Solved,
+    /// Gave another answer than the recorded one, or none at all.
+    Failing,
+}
+
+impl State {
+    fn symbol(&self) -> char {
+        match self {
+            State::Stub => '.',
+            State::Unsolved => '?',
+            State::Solved => '*',
+            State::Failing => '!',
+        }
+    }
+}
+
+/// What is wrong with the example test of a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExampleTest {
+    Placeholder,
+    CommentedOut,
+    Missing,
+}
+
+impl std::fmt::Display for ExampleTest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            ExampleTest::Placeholder => "example test expects the 0000 placeholder",
+            ExampleTest::CommentedOut => "example test commented out",
+            ExampleTest::Missing => "no example test",
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayStatus {
+    pub year: u32,
+    pub day: u32,
+    pub parts: [State; 2],
+    pub notes: Vec<ExampleTest>,
+}
+
+impl DayStatus {
+    pub fn state(&self, part: Part) -> State {
+        match part {
+            Part::One => self.parts[0],
+            Part::Two => self.parts[1],
+        }
+    }
+}
+
+lazy_static! {
+    static ref STUB: Regex = Regex::new(r#"fn part([12])\(_?input: &str\) -> String \{\s*"0"\.to_string\(\)\s*\}"#).unwrap();
+    static ref EXAMPLE: Regex = Regex::new(r"(?m)^\s*crate::test_examples?_aoc!").unwrap();
+    static ref COMMENTED: Regex = Regex::new(r"(?m)^\s*//\s*crate::test_examples?_aoc!").unwrap();
+    static ref PLACEHOLDER: Regex = Regex::new(r"(?m)^\s*crate::test_example_aoc!\((?:0000,.*|.*, 0000)\);").unwrap();
+}
+
+/// The status of a day from the source of its module and its recorded answers.
+pub fn day(year: u32, day: u32, source: &str, expected: &Expected) -> DayStatus {
+    let stubs = STUB.captures_iter(source).map(|captures| captures[1].to_owned()).collect::<Vec<_>>();
+    let state = |part: Part, number: &str| {
+        if stubs.iter().any(|stub| stub == number) {
+            State::Stub
+        } else if expected.get(part).is_some() {
+            State::Solved
+        } else {
+            State::Unsolved
+        }
+    };
+
+    let mut notes = Vec::new();
+    if PLACEHOLDER.is_match(source) {
+        notes.push(ExampleTest::Placeholder);
+    }
+    if COMMENTED.is_match(source) {
+        notes.push(ExampleTest::CommentedOut);
+    } else if !EXAMPLE.is_match(source) {
+        notes.push(ExampleTest::Missing);
+    }
+
+    DayStatus { year, day, parts: [state(Part::One, "1"), state(Part::Two, "2")], notes }
+}
+
+/// The status of the selected days, read from their module under the `src`
+/// of the source root and the answers of their year. Day 25 has no second
+/// puzzle: its star comes with all the others.
+pub fn collect(selected: &[Selected]) -> Result<Vec<DayStatus>, String> {
+    let mut statuses = Vec::new();
+    for Selected { solution, .. } in selected {
+        let path = format!("src/year{}/day{:02}.rs", solution.year, solution.day);
+        let path = input::source_root().join(path);
+        let source = std::fs::read_to_string(&path).map_err(|error| format!("{}: {}", path.display(), error))?;
+        statuses.push(day(solution.year, solution.day, &source, &answers::expected(solution.year, solution.day)?));
+    }
+
+    let solved = |year: u32, statuses: &[DayStatus]| {
+        statuses
+            .iter()
+            .filter(|status| status.year == year)
+            .flat_map(|status| status.parts.iter().map(move |state| (status.day, state)))
+            .filter(|&(day, _)| day != 25)
+            .all(|(_, &state)| state == State::Solved)
+    };
+    for index in 0..statuses.len() {
+        let status = &statuses[index];
+        if status.day == 25 && status.parts[1] == State::Unsolved && solved(status.year, &statuses) {
+            statuses[index].parts[1] = State::Solved;
+        }
+    }
+    Ok(statuses)
+}
+
+/// Mark the parts that gave a wrong answer, or none, when run.
+pub fn check(statuses: &mut [DayStatus], rows: &[Row]) {
+    for row in rows {
+        let Some(status) = statuses.iter_mut().find(|status| (status.year, status.day) == (row.year, row.day)) else {
+            continue;
+        };
+        for (state, result) in status.parts.iter_mut().zip([&row.part1, &row.part2]) {
+            if result.as_ref().is_some_and(|result| matches!(result.check, Check::Wrong | Check::Error)) {
+                *state = State::Failing;
+            }
+        }
+    }
+}
+
+/// A row of stars per year, a column per day, then the notes of the days
+/// that are not only stubs.
+pub fn format(statuses: &[DayStatus]) -> String {
+    let mut years = statuses.iter().map(|status| status.year).collect::<Vec<_>>();
+    years.dedup();
+
+    let mut lines = vec![format!("     {}", (1..=25).map(|day| format!(" {:02}", day)).collect::<String>())];
+    for year in years {
+        let mut line = format!("{}  ", year);
+        let mut stars = 0;
+        for day in 1..=25 {
+            match statuses.iter().find(|status| (status.year, status.day) == (year, day)) {
+                Some(status) => {
+                    line.extend(status.parts.iter().map(State::symbol));
+                    stars += status.parts.iter().filter(|&&state| state == State::Solved).count();
+                }
+                None => line.push_str("  "),
+            }
+            line.push(' ');
+        }
+        lines.push(format!("{}{:>2}/50", line, stars));
+    }
+    lines.push("* solved  ? no recorded answer  . stub  ! failing".to_owned());
+
+    for status in statuses {
+        if status.parts.iter().all(|&state| state == State::Stub) {
+            continue;
+        }
+        for note in &status.notes {
+            lines.push(format!("{:04}.{:02} {}", status.year, status.day, note));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEMPLATE: &str = "fn part1(input: &str) -> String {\n    \"0\".to_string()\n}\n\nfn part2(input: &str) -> String {\n    \"0\".to_string()\n}\n\ncrate::run!();\n\ncrate::test_example_aoc!(0000, 0000);\n\ncrate::test_aoc!();";
+
+    #[test]
+    fn detect_stubs() {
+        let stub = day(2023, 5, TEMPLATE, &Expected::default());
+        assert_eq!(stub.parts, [State::Stub, State::Stub]);
+        assert_eq!(stub.notes, vec![ExampleTest::Placeholder]);
+
+        let source = TEMPLATE.replacen("\"0\".to_string()", "input.len().to_string()", 1).replace("crate::test_example", "// crate::test_example");
+        let expected = Expected { part1: Some("7".to_owned()), part2: None };
+        let started = day(2023, 6, &source, &expected);
+        assert_eq!(started.parts, [State::Solved, State::Stub]);
+        assert_eq!(started.notes, vec![ExampleTest::CommentedOut]);
+        assert_eq!(day(2023, 5, "crate::test_aoc!();", &expected).parts, [State::Solved, State::Unsolved]);
+        assert_eq!(day(2023, 5, "crate::test_aoc!();", &expected).notes, vec![ExampleTest::Missing]);
+
+        let grid = format(&[stub, started]);
+        assert_eq!(grid.lines().nth(1).unwrap(), format!("2023  {}.. *. {} 1/50", " ".repeat(12), " ".repeat(57)));
+        assert!(grid.ends_with("\n2023.06 example test commented out"));
+    }
+
+    #[test]
+    fn collect_2022() {
+        let selected = crate::selector::select(&["2022".parse().unwrap()]).unwrap();
+        let statuses = collect(&selected).unwrap();
+        assert!(statuses.iter().all(|status| status.parts == [State::Solved, State::Solved]));
+    }
+}
@@ -0,0 +1,177 @@
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+use crate::config::Config;
+use crate::input;
+
+/// Where the puzzle inputs are kept, since they must not be published:
+/// plain in `rsc`, encrypted next to where the plain ones would be, or in a
+/// directory out of the repository.
+#[derive(Clone, Debug, Default)]
+pub struct Storage {
+    /// Hex of the 32 bytes key of the encrypted inputs (`AOC_INPUT_KEY`).
+    key: Option<String>,
+    /// Directory holding the inputs instead of `rsc` (`AOC_INPUT_DIR`).
+    dir: Option<PathBuf>,
+}
+
+const NONCE_LENGTH: usize = 12;
+
+/// The storage set up by `aoc.toml` and the environment, or why they are
+/// invalid.
+pub fn get() -> Result<&'static Storage, String> {
+    static STORAGE: OnceLock<Result<Storage, String>> = OnceLock::new();
+    STORAGE
+        .get_or_init(|| Config::load().map(|config| Storage::new(config.input_key, config.input_dir)))
+        .as_ref()
+        .map_err(Clone::clone)
+}
+
+/// Where the encrypted input of a day is kept, as hex.
+pub fn encrypted_path(year: u32, day: u32) -> PathBuf {
+    input::puzzle_path(year, day).with_extension("txt.enc")
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn unhex(text: &str) -> Result<Vec<u8>, String> {
+    let text = text.trim();
+    if !text.len().is_multiple_of(2) || !text.is_ascii() {
+        return Err("invalid hex".to_owned());
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&text[index..index + 2], 16).map_err(|_| "invalid hex".to_owned()))
+        .collect()
+}
+
+/// A new random key, as hex.
+pub fn generate_key() -> String {
+    hex(&ChaCha20Poly1305::generate_key(&mut OsRng))
+}
+
+fn cipher(key: &str) -> Result<ChaCha20Poly1305, String> {
+    let key = unhex(key).ok().filter(|key| key.len() == 32).ok_or("the key must be 64 hex digits")?;
+    Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+}
+
+/// Encrypt an input, giving the hex of a random nonce followed by the ciphertext.
+pub fn encrypt(key: &str, input: &str) -> Result<String, String> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher(key)?.encrypt(&nonce, input.as_bytes()).map_err(|_| "cannot encrypt")?;
+    Ok(hex(&nonce) + &hex(&ciphertext) + "\n")
+}
+
+pub fn decrypt(key: &str, encrypted: &str) -> Result<String, String> {
+    let data = unhex(encrypted)?;
+    if data.len() < NONCE_LENGTH {
+        return Err("truncated".to_owned());
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LENGTH);
+    let input = cipher(key)?
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "wrong key, or corrupted file")?;
+    String::from_utf8(input).map_err(|error| error.to_string())
+}
+
+impl Storage {
+    pub fn new(key: Option<String>, dir: Option<PathBuf>) -> Self {
+        Self { key, dir }
+    }
+
+    /// Where the plain input of a day is read, and written when downloaded.
+    pub fn plain_path(&self, year: u32, day: u32) -> PathBuf {
+        match &self.dir {
+            Some(dir) => dir.join(format!("year{}/day{:02}.txt", year, day)),
+            None => input::resolve(input::puzzle_path(year, day)),
+        }
+    }
+
+    /// Where the input of a day already is, if it is kept: its plain file, the
+    /// empty placeholders created by `scaffold` not counting, or its encrypted
+    /// one when the inputs are kept in `rsc`.
+    pub fn cached(&self, year: u32, day: u32) -> Option<PathBuf> {
+        let plain = self.plain_path(year, day);
+        if std::fs::metadata(&plain).is_ok_and(|metadata| metadata.len() > 0) {
+            return Some(plain);
+        }
+        let encrypted = input::resolve(encrypted_path(year, day));
+        (self.dir.is_none() && encrypted.is_file()).then_some(encrypted)
+    }
+
+    /// Read the puzzle input of a day: the plain file when there is one,
+    /// otherwise the encrypted one. An input that is not there, or that
+    /// cannot be decrypted without a key, is `NotFound`.
+    pub fn read(&self, year: u32, day: u32) -> std::io::Result<String> {
+        if self.dir.is_some() {
+            let path = self.plain_path(year, day);
+            return std::fs::read_to_string(&path)
+                .map_err(|error| std::io::Error::new(error.kind(), format!("{}: {}", path.display(), error)));
+        }
+        let missing = match input::read(input::puzzle_path(year, day)) {
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => error,
+            read => return read,
+        };
+        let path = encrypted_path(year, day);
+        let encrypted = match input::read(&path) {
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Err(missing),
+            encrypted => encrypted?,
+        };
+        let key = self.key.as_deref().ok_or_else(|| {
+            let message = format!("{} is encrypted: set AOC_INPUT_KEY or `input_key` in aoc.toml", path.display());
+            std::io::Error::new(std::io::ErrorKind::NotFound, message)
+        })?;
+        decrypt(key, &encrypted).map_err(|error| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{}: {}", path.display(), error))
+        })
+    }
+
+    /// Encrypt the plain input of a day next to it, returning where.
+    pub fn encrypt(&self, year: u32, day: u32) -> Result<PathBuf, String> {
+        let key = self.key.as_deref().ok_or_else(|| {
+            format!("no key: set AOC_INPUT_KEY or `input_key` in aoc.toml, for instance to {}", generate_key())
+        })?;
+        let plain = self.plain_path(year, day);
+        let input = std::fs::read_to_string(&plain).map_err(|error| format!("{}: {}", plain.display(), error))?;
+        let path = input::resolve(encrypted_path(year, day));
+        std::fs::write(&path, encrypt(key, &input)?).map_err(|error| format!("{}: {}", path.display(), error))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn round_trip() {
+        let encrypted = encrypt(KEY, ">>><<><>\n").unwrap();
+        assert_ne!(encrypted, encrypt(KEY, ">>><<><>\n").unwrap());
+        assert_eq!(decrypt(KEY, &encrypted).unwrap(), ">>><<><>\n");
+        assert_eq!(decrypt(&generate_key(), &encrypted), Err("wrong key, or corrupted file".to_owned()));
+        assert!(encrypt("0011", "").is_err());
+    }
+
+    #[test]
+    fn read_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc-storage-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("year2022")).unwrap();
+        std::fs::write(dir.join("year2022/day01.txt"), "1000\n").unwrap();
+        std::fs::write(dir.join("year2022/day03.txt"), "").unwrap();
+        let storage = Storage::new(None, Some(dir.clone()));
+        assert_eq!(storage.read(2022, 1).unwrap(), "1000\n");
+        assert_eq!(storage.cached(2022, 1), Some(dir.join("year2022/day01.txt")));
+        assert_eq!((storage.cached(2022, 2), storage.cached(2022, 3)), (None, None));
+        assert_eq!(storage.read(2022, 2).unwrap_err().kind(), std::io::ErrorKind::NotFound);
+        std::fs::remove_dir_all(dir).unwrap();
+
+        assert_eq!(Storage::default().read(2022, 26).unwrap_err().kind(), std::io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,280 @@
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::client::Client;
+use crate::solution::Part;
+
+/// What the site said about a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Submitted too soon after the previous answer; it was not checked.
+    Wait,
+    /// The part is already solved; the answer was not checked.
+    AlreadySolved,
+}
+
+impl Verdict {
+    fn is_rejection(&self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Verdict::Correct => "that's the right answer",
+            Verdict::Wrong => "that's not the right answer",
+            Verdict::TooHigh => "that's not the right answer, it is too high",
+            Verdict::TooLow => "that's not the right answer, it is too low",
+            Verdict::Wait => "an answer was given too recently",
+            Verdict::AlreadySolved => "this part is already solved",
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Response {
+    pub verdict: Verdict,
+    /// How long to wait before the next submission.
+    pub wait: Option<Duration>,
+}
+
+lazy_static! {
+    static ref LEFT_TO_WAIT: Regex = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    static ref WAIT_MINUTES: Regex = Regex::new(r"(?i)wait (one|\d+) minutes?").unwrap();
+}
+
+/// Read the verdict out of the page returned for a submitted answer.
+pub fn parse_response(page: &str) -> Result<Response, String> {
+    let verdict = if page.contains("That's the right answer") {
+        Verdict::Correct
+    } else if page.contains("That's not the right answer") {
+        if page.contains("too high") {
+            Verdict::TooHigh
+        } else if page.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if page.contains("You gave an answer too recently") {
+        Verdict::Wait
+    } else if page.contains("Did you already complete it?") {
+        Verdict::AlreadySolved
+    } else {
+        return Err("unexpected response to the submitted answer".to_owned());
+    };
+
+    let wait = if let Some(captures) = LEFT_TO_WAIT.captures(page) {
+        let minutes = captures.get(1).map_or(0, |minutes| minutes.as_str().parse::<u64>().unwrap());
+        Some(Duration::from_secs(minutes * 60 + captures[2].parse::<u64>().unwrap()))
+    } else {
+        WAIT_MINUTES.captures(page).map(|captures| {
+            let minutes = match &captures[1] {
+                "one" => 1,
+                minutes => minutes.parse::<u64>().unwrap(),
+            };
+            Duration::from_secs(minutes * 60)
+        })
+    };
+
+    Ok(Response { verdict, wait })
+}
+
+/// A submitted answer, as kept in the guess log of its year.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Guess {
+    pub timestamp: u64,
+    pub day: u32,
+    /// 1 or 2, as the `level` of the submission.
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Time before which no answer should be submitted again.
+    pub wait_until: Option<u64>,
+}
+
+pub fn guesses_path(year: u32) -> PathBuf {
+    PathBuf::from(format!("rsc/year{}/guesses.jsonl", year))
+}
+
+/// Load the guess log; a missing file means nothing was submitted yet.
+pub fn load(path: &Path) -> Result<Vec<Guess>, String> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(format!("{}: {}", path.display(), error)),
+    };
+    std::io::BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+        .map(|(index, line)| {
+            let line = line.map_err(|error| format!("{}: {}", path.display(), error))?;
+            serde_json::from_str(&line).map_err(|error| format!("{}:{}: {}", path.display(), index + 1, error))
+        })
+        .collect()
+}
+
+fn append(path: &Path, guess: &Guess) -> Result<(), String> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|error| format!("{}: {}", path.display(), error))?;
+    writeln!(file, "{}", serde_json::to_string(guess).unwrap()).map_err(|error| format!("{}: {}", path.display(), error))
+}
+
+fn level(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+/// Refuse an answer that the guesses already tell about, or that would be
+/// submitted before the end of a wait the site asked for.
+pub fn check(guesses: &[Guess], day: u32, part: Part, answer: &str, now: u64) -> Result<(), String> {
+    if let Some(wait_until) = guesses.iter().filter_map(|guess| guess.wait_until).max().filter(|&until| until > now) {
+        return Err(format!("wait {}s before submitting again", wait_until - now));
+    }
+
+    let guesses = guesses.iter().filter(|guess| guess.day == day && guess.part == level(part)).collect::<Vec<_>>();
+    if let Some(guess) = guesses.iter().find(|guess| guess.verdict == Verdict::Correct) {
+        return Err(format!("already solved with {}", guess.answer));
+    }
+    if let Some(guess) = guesses.iter().find(|guess| guess.answer == answer && guess.verdict.is_rejection()) {
+        return Err(format!("{} was already rejected: {}", answer, guess.verdict));
+    }
+    if let Ok(value) = answer.parse::<i64>() {
+        for guess in &guesses {
+            match (guess.verdict, guess.answer.parse::<i64>()) {
+                (Verdict::TooHigh, Ok(high)) if value >= high => {
+                    return Err(format!("{} is too high, {} already was", answer, high))
+                }
+                (Verdict::TooLow, Ok(low)) if value <= low => {
+                    return Err(format!("{} is too low, {} already was", answer, low))
+                }
+                _ => (),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Submit an answer unless the guess log at `log` rules it out, and record
+/// the guess with the verdict, including the wait the site asked for.
+pub fn submit(client: &mut Client, log: &Path, year: u32, day: u32, part: Part, answer: &str) -> Result<Response, String> {
+    let now = now();
+    check(&load(log)?, day, part, answer, now)?;
+
+    let response = parse_response(&client.answer(year, day, level(part), answer)?)?;
+    append(
+        log,
+        &Guess {
+            timestamp: now,
+            day,
+            part: level(part),
+            answer: answer.to_owned(),
+            verdict: response.verdict,
+            wait_until: response.wait.map(|wait| now + wait.as_secs()),
+        },
+    )?;
+    Ok(response)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::stub;
+    use crate::config::Config;
+
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>";
+    const TOO_RECENTLY: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 38s left to wait. <a href=\"/2022/day/1\">[Return to Day 1]</a></p></article>";
+    const RIGHT: &str = "<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to collecting enough star fruit.</p></article>";
+
+    #[test]
+    fn responses() {
+        let response = |verdict, wait: Option<u64>| Ok(Response { verdict, wait: wait.map(Duration::from_secs) });
+        assert_eq!(parse_response(TOO_HIGH), response(Verdict::TooHigh, Some(60)));
+        assert_eq!(parse_response(TOO_RECENTLY), response(Verdict::Wait, Some(98)));
+        assert_eq!(parse_response(RIGHT), response(Verdict::Correct, None));
+        assert_eq!(
+            parse_response("<p>That's not the right answer.  Please wait 5 minutes before trying again.</p>"),
+            response(Verdict::Wrong, Some(300))
+        );
+        assert_eq!(
+            parse_response("You don't seem to be solving the right level.  Did you already complete it?"),
+            response(Verdict::AlreadySolved, None)
+        );
+        assert!(parse_response("<html></html>").is_err());
+    }
+
+    fn guess(part: u8, answer: &str, verdict: Verdict, wait_until: Option<u64>) -> Guess {
+        Guess { timestamp: 100, day: 1, part, answer: answer.to_owned(), verdict, wait_until }
+    }
+
+    #[test]
+    fn known_answers() {
+        let guesses = vec![
+            guess(1, "500", Verdict::TooHigh, Some(160)),
+            guess(1, "100", Verdict::TooLow, None),
+            guess(1, "ABC", Verdict::Wrong, None),
+            guess(2, "42", Verdict::Correct, None),
+        ];
+        assert_eq!(check(&guesses, 1, Part::One, "300", 120), Err("wait 40s before submitting again".to_owned()));
+        assert_eq!(check(&guesses, 1, Part::One, "300", 200), Ok(()));
+        assert!(check(&guesses, 1, Part::One, "ABC", 200).is_err());
+        assert_eq!(check(&guesses, 1, Part::One, "600", 200), Err("600 is too high, 500 already was".to_owned()));
+        assert_eq!(check(&guesses, 1, Part::One, "100", 200), Err("100 was already rejected: that's not the right answer, it is too low".to_owned()));
+        assert_eq!(check(&guesses, 1, Part::Two, "43", 200), Err("already solved with 42".to_owned()));
+        assert_eq!(check(&guesses, 2, Part::One, "600", 200), Ok(()));
+    }
+
+    #[test]
+    fn submit_and_log() {
+        let (url, server) = stub::serve(vec![(200, TOO_HIGH)]);
+        let dir = std::env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config = Config {
+            session: Some("53616c7465".to_owned()),
+            url: Some(url),
+            delay: Some(0),
+            input_dir: Some(dir.clone()),
+            ..Config::default()
+        };
+        let mut client = Client::new(&config).unwrap();
+        let log = dir.join("guesses.jsonl");
+
+        let response = submit(&mut client, &log, 2022, 1, Part::Two, "71300").unwrap();
+        assert_eq!(response.verdict, Verdict::TooHigh);
+        let guesses = load(&log).unwrap();
+        assert_eq!((guesses[0].part, guesses[0].answer.as_str(), guesses[0].verdict), (2, "71300", Verdict::TooHigh));
+        assert_eq!(guesses[0].wait_until, Some(guesses[0].timestamp + 60));
+        assert!(submit(&mut client, &log, 2022, 1, Part::Two, "80000").unwrap_err().starts_with("wait "));
+        assert_eq!(
+            check(&guesses, 1, Part::Two, "80000", guesses[0].timestamp + 60),
+            Err("80000 is too high, 71300 already was".to_owned())
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2022/day/1/answer HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=71300"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+
+use crate::answers::{self, Check};
+use crate::bench::format_duration;
+use crate::input;
+use crate::selector::Selected;
+use crate::solution::Part;
+
+/// The files that a day depends on: its module, inputs, examples and answers.
+fn watched(year: u32, day: u32) -> Vec<PathBuf> {
+    let example = input::example_path(year, day, None);
+    vec![
+        PathBuf::from(format!("src/year{}/day{:02}.rs", year, day)),
+        input::puzzle_path(year, day),
+        example.with_extension("examples.toml"),
+        example,
+        input::example_path(year, day, Some(Part::One)),
+        input::example_path(year, day, Some(Part::Two)),
+        answers::path(year),
+    ]
+}
+
+/// Modification time of each file, `None` for those that do not exist.
+fn snapshot(paths: &[PathBuf]) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| {
+            let resolved = if path.starts_with("src") {
+                input::source_root().join(path)
+            } else {
+                input::resolve(path)
+            };
+            let modified = std::fs::metadata(resolved).and_then(|metadata| metadata.modified());
+            (path.clone(), modified.ok())
+        })
+        .collect()
+}
+
+/// A part, as read from the JSON report of a run.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+struct Ran {
+    answer: Option<String>,
+    error: Option<String>,
+    panic: Option<String>,
+    timeout: Option<String>,
+    check: Check,
+    time_ns: u64,
+}
+
+impl Ran {
+    fn outcome(&self) -> &str {
+        let failure = self.error.as_ref().or(self.panic.as_ref()).or(self.timeout.as_ref());
+        self.answer.as_deref().or(failure.map(String::as_str)).unwrap_or("")
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct RanDay {
+    year: u32,
+    day: u32,
+    part1: Option<Ran>,
+    part2: Option<Ran>,
+}
+
+/// One line telling what a part gave, and how it differs from the previous run.
+fn describe(name: &str, previous: Option<&Ran>, ran: &Ran) -> String {
+    let time = Duration::from_nanos(ran.time_ns);
+    let mut line = format!("{} {} {} {}", name, ran.check.symbol(), ran.outcome(), format_duration(time));
+    match previous {
+        None => (),
+        Some(previous) if previous.outcome() != ran.outcome() => {
+            line.push_str(&format!(" (was {})", previous.outcome()));
+        }
+        Some(previous) if previous.time_ns > 0 => {
+            let change = (ran.time_ns as f64 / previous.time_ns as f64 - 1.0) * 100.0;
+            line.push_str(&format!(" ({:+.1}%)", change));
+        }
+        Some(_) => (),
+    }
+    line
+}
+
+/// A cargo subcommand, built with the profile of the running binary.
+fn cargo(subcommand: &str) -> Command {
+    let mut command = Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command.current_dir(input::source_root()).args([subcommand, "--quiet"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    command
+}
+
+/// Rebuild and run the example tests of the selected days, then, when they
+/// pass, run the days on their puzzle inputs and print what changed since
+/// `last`, the parts of the previous run.
+fn run_once(selectors: &[String], selected: &[Selected], last: &mut BTreeMap<String, Ran>) {
+    let filters = selected
+        .iter()
+        .map(|Selected { solution, .. }| format!("year{}::day{:02}::example", solution.year, solution.day));
+    match cargo("test").arg("--").args(filters).status() {
+        Ok(status) if status.success() => (),
+        Ok(_) => return println!("example tests failed"),
+        Err(error) => return println!("cannot run cargo: {}", error),
+    }
+
+    let output = cargo("run").arg("--").args(selectors).args(["--format", "json"]).stderr(Stdio::inherit()).output();
+    let days = match output.map(|output| serde_json::from_slice::<Vec<RanDay>>(&output.stdout)) {
+        Ok(Ok(days)) => days,
+        Ok(Err(error)) => return println!("cannot read the answers: {}", error),
+        Err(error) => return println!("cannot run cargo: {}", error),
+    };
+    for day in days {
+        for (part, ran) in [(Part::One, day.part1), (Part::Two, day.part2)] {
+            if let Some(ran) = ran {
+                let name = format!("{:04}.{:02}.{}", day.year, day.day, part);
+                println!("{}", describe(&name, last.get(&name), &ran));
+                last.insert(name, ran);
+            }
+        }
+    }
+}
+
+/// Run the selected days, then again every time one of their files changes,
+/// checking for changes every `interval`.
+pub fn watch(selectors: &[String], selected: &[Selected], interval: Duration) -> ! {
+    let paths = selected
+        .iter()
+        .flat_map(|Selected { solution, .. }| watched(solution.year, solution.day))
+        .collect::<Vec<_>>();
+    let mut last = BTreeMap::new();
+    let mut files = snapshot(&paths);
+    run_once(selectors, selected, &mut last);
+    loop {
+        std::thread::sleep(interval);
+        let current = snapshot(&paths);
+        let changed = current.iter().filter(|(path, time)| files.get(*path) != Some(time)).collect::<Vec<_>>();
+        if changed.is_empty() {
+            continue;
+        }
+        for (path, _) in changed {
+            println!("-- {} changed", path.display());
+        }
+        files = current;
+        run_once(selectors, selected, &mut last);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ran(answer: &str, check: Check, time_ns: u64) -> Ran {
+        Ran { answer: Some(answer.to_owned()), error: None, panic: None, timeout: None, check, time_ns }
+    }
+
+    #[test]
+    fn describe_changes() {
+        let first = ran("1651", Check::Unknown, 2_000_000);
+        assert_eq!(describe("2022.16.p1", None, &first), "2022.16.p1 ? 1651 2.00 ms");
+        let faster = ran("1651", Check::Unknown, 1_500_000);
+        assert_eq!(describe("2022.16.p1", Some(&first), &faster), "2022.16.p1 ? 1651 1.50 ms (-25.0%)");
+        let failed = Ran { answer: None, error: Some("2022.16.p1: no valve AA".to_owned()), ..ran("", Check::Error, 0) };
+        assert_eq!(describe("2022.16.p1", Some(&faster), &failed), "2022.16.p1 ! 2022.16.p1: no valve AA 0 ns (was 1651)");
+    }
+
+    #[test]
+    fn read_report() {
+        let report = r#"[{"year":2022,"day":16,"title":null,"part1":{"answer":"1651","check":"correct","time_ns":5,"cpu_ns":null},"part2":null}]"#;
+        let days = serde_json::from_str::<Vec<RanDay>>(report).unwrap();
+        assert_eq!(days[0].part1, Some(ran("1651", Check::Correct, 5)));
+        assert!(days[0].part2.is_none());
+        assert_eq!(watched(2022, 16)[2], PathBuf::from("rsc/year2022/example/day16.examples.toml"));
+    }
+}
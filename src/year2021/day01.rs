@@ -1,33 +1,33 @@
-fn parse_input(input: &str) -> Vec<u32> {
-    input.lines()
-        .map(|line| line.parse::<u32>().unwrap())
-        .collect::<Vec<_>>()
+use crate::error::{parse_lines, AocError};
+
+fn parse_input(input: &str) -> Result<Vec<u32>, AocError> {
+    parse_lines(input, |line| line.parse::<u32>().map_err(|_| AocError::new(format!("invalid depth '{}'", line))))
 }
 
-fn part1(input: &str) -> String {
-    let numbers = parse_input(input);
+fn part1(input: &str) -> Result<String, AocError> {
+    let numbers = parse_input(input)?;
     let mut higher = 0;
     for i in 1..numbers.len() {
         if numbers[i] > numbers[i-1] {
             higher += 1;
         }
     }
-    higher.to_string()
+    Ok(higher.to_string())
 }
 
-fn part2(input: &str) -> String {
-    let numbers = parse_input(input);
+fn part2(input: &str) -> Result<String, AocError> {
+    let numbers = parse_input(input)?;
     let mut higher = 0;
     for i in 3..numbers.len() {
         if numbers[i] > numbers[i - 3] {
             higher += 1;
         }
     }
-    higher.to_string()
+    Ok(higher.to_string())
 }
 
-crate::run!();
+crate::run!("Sonar Sweep", parse_input);
 
 crate::test_example_aoc!(7, 5);
 
-crate::test_aoc!(1759, 1805);
\ No newline at end of file
+crate::test_aoc!();
\ No newline at end of file
@@ -6,8 +6,8 @@ fn part2(input: &str) -> String {
     "0".to_string()
 }
 
-crate::run!();
+crate::run!("Dive!");
 
 crate::test_example_aoc!(0000, 0000);
 
-crate::test_aoc!(0000, 0000);
+crate::test_aoc!();
@@ -1,31 +1,35 @@
-fn preprocess(input: &str) -> Vec<u32> {
+use crate::error::AocError;
+
+fn preprocess(input: &str) -> Result<Vec<u32>, AocError> {
     let mut current = 0;
     let mut elves = Vec::new();
-    for line in input.lines() {
+    for (index, line) in input.lines().enumerate() {
         if line.is_empty() {
             elves.push(current);
             current = 0;
         } else {
-            current += line.parse::<u32>().unwrap();
+            current += line
+                .parse::<u32>()
+                .map_err(|_| AocError::new(format!("invalid calories '{}'", line)).at_line(index + 1))?;
         }
     }
     elves.push(current);
     elves.sort();
-    elves
+    Ok(elves)
 }
 
-fn part1(input: &str) -> String {
-    let elves = preprocess(input);
-    (*elves.last().unwrap()).to_string()
+fn part1(input: &str) -> Result<String, AocError> {
+    let elves = preprocess(input)?;
+    Ok((*elves.last().unwrap()).to_string())
 }
 
-fn part2(input: &str) -> String {
-    let elves = preprocess(input);
-    elves[elves.len() - 3..].iter().sum::<u32>().to_string()
+fn part2(input: &str) -> Result<String, AocError> {
+    let elves = preprocess(input)?;
+    Ok(elves.iter().rev().take(3).sum::<u32>().to_string())
 }
 
-crate::run!();
+crate::run!("Calorie Counting", preprocess);
 
 crate::test_example_aoc!(24000, 45000);
 
-crate::test_aoc!(71300, 209691);
+crate::test_aoc!();
@@ -1,3 +1,5 @@
+use crate::error::{parse_lines, AocError};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum HandShape {
     Rock,
@@ -22,14 +24,7 @@ impl HandShape {
         }
     }
 
-    fn from_letter(letter: char) -> Self {
-        match letter {
-            'A' | 'X' => HandShape::Rock,
-            'B' | 'Y' => HandShape::Paper,
-            'C' | 'Z' => HandShape::Scissor,
-            _ => panic!("Unknown letter {}", letter),
-        }
-    }
+    const ALL: [HandShape; 3] = [HandShape::Rock, HandShape::Paper, HandShape::Scissor];
 
     fn get_score(&self, opponent: HandShape) -> u32 {
         let fight_points = if *self == opponent.win_against() {
@@ -50,46 +45,47 @@ impl HandShape {
     }
 }
 
-fn preprocess(input: &str) -> Vec<(char, char)> {
-    input
-        .lines()
-        .map(|line| (line.chars().next().unwrap(), line.chars().nth(2).unwrap()))
-        .collect()
+/// The shape of the opponent, and the index of the second letter among `X`,
+/// `Y` and `Z`.
+fn preprocess(input: &str) -> Result<Vec<(HandShape, usize)>, AocError> {
+    parse_lines(input, |line| {
+        let bytes = line.as_bytes();
+        match bytes {
+            [opponent @ b'A'..=b'C', b' ', second @ b'X'..=b'Z'] => {
+                Ok((HandShape::ALL[(opponent - b'A') as usize], (second - b'X') as usize))
+            }
+            _ => Err(AocError::new(format!("invalid round '{}'", line))),
+        }
+    })
 }
 
-fn part1(input: &str) -> String {
-    let rounds = preprocess(input);
-    rounds
+fn part1(input: &str) -> Result<String, AocError> {
+    let rounds = preprocess(input)?;
+    Ok(rounds
         .iter()
-        .map(|round| {
-            let opponent = HandShape::from_letter(round.0);
-            let player = HandShape::from_letter(round.1);
+        .map(|&(opponent, second)| {
+            let player = HandShape::ALL[second];
             player.get_score(opponent)
         })
         .sum::<u32>()
-        .to_string()
+        .to_string())
 }
 
-fn part2(input: &str) -> String {
-    let rounds = preprocess(input);
-    rounds
+fn part2(input: &str) -> Result<String, AocError> {
+    let rounds = preprocess(input)?;
+    Ok(rounds
         .iter()
-        .map(|round| {
-            let opponent = HandShape::from_letter(round.0);
-            let player = match round.1 {
-                'X' => opponent.win_against(),
-                'Y' => opponent,
-                'Z' => opponent.lose_against(),
-                letter => panic!("Unknown letter {}", letter),
-            };
+        .map(|&(opponent, second)| {
+            // Lose, draw or win
+            let player = [opponent.win_against(), opponent, opponent.lose_against()][second];
             player.get_score(opponent)
         })
         .sum::<u32>()
-        .to_string()
+        .to_string())
 }
 
-crate::run!();
+crate::run!("Rock Paper Scissors", preprocess);
 
 crate::test_example_aoc!(15, 12);
 
-crate::test_aoc!(14375, 10274);
+crate::test_aoc!();
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use crate::error::{parse_lines, AocError};
+
 fn letter_score(letter: char) -> u32 {
     match letter {
         'a'..='z' => 1 + letter as u32 - ('a' as u32),
@@ -8,40 +10,46 @@ fn letter_score(letter: char) -> u32 {
     }
 }
 
-fn part1(input: &str) -> String {
-    input
-        .lines()
-        .map(|line| {
-            let size = line.len() / 2;
-            let bag1 = line[..size].chars().collect::<HashSet<char>>();
-            let bag2 = line[size..].chars().collect::<HashSet<char>>();
-            bag1.intersection(&bag2).cloned().next().unwrap()
-        })
-        .map(letter_score)
-        .sum::<u32>()
-        .to_string()
+/// The item found in all the bags.
+fn common_item(bags: &[&str]) -> Result<char, AocError> {
+    let mut common = bags[0].chars().collect::<HashSet<char>>();
+    for bag in &bags[1..] {
+        let items = bag.chars().collect::<HashSet<char>>();
+        common.retain(|item| items.contains(item));
+    }
+    common
+        .into_iter()
+        .next()
+        .ok_or_else(|| AocError::new(format!("no item common to '{}'", bags.join("', '"))))
 }
 
-fn part2(input: &str) -> String {
-    input
-        .lines()
-        .collect::<Vec<_>>()
-        .chunks(3)
-        .map(|lines| {
-            let elf1 = lines[0].chars().collect::<HashSet<char>>();
-            let elf2 = lines[1].chars().collect::<HashSet<char>>();
-            let elf3 = lines[2].chars().collect::<HashSet<char>>();
-            let common = elf1.intersection(&elf2).cloned().collect::<HashSet<_>>();
-            let common = common.intersection(&elf3);
-            common.cloned().next().unwrap()
-        })
-        .map(letter_score)
-        .sum::<u32>()
-        .to_string()
+fn part1(input: &str) -> Result<String, AocError> {
+    let items = parse_lines(input, |line| {
+        let size = line.len() / 2;
+        if !line.is_char_boundary(size) {
+            return Err(AocError::new(format!("invalid rucksack '{}'", line)));
+        }
+        common_item(&[&line[..size], &line[size..]])
+    })?;
+    Ok(items.into_iter().map(letter_score).sum::<u32>().to_string())
+}
+
+fn part2(input: &str) -> Result<String, AocError> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let mut score = 0;
+    for (index, group) in lines.chunks(3).enumerate() {
+        let item = if group.len() == 3 {
+            common_item(group)
+        } else {
+            Err(AocError::new("incomplete group of elves"))
+        };
+        score += letter_score(item.map_err(|error| error.at_line(3 * index + 1))?);
+    }
+    Ok(score.to_string())
 }
 
-crate::run!();
+crate::run!("Rucksack Reorganization");
 
 crate::test_example_aoc!(157, 70);
 
-crate::test_aoc!(8105, 2363);
+crate::test_aoc!();
@@ -1,26 +1,25 @@
-fn preprocess(input: &str) -> Vec<((u32, u32), (u32, u32))> {
-    let re = regex::Regex::new(r"(\d+)-(\d+),(\d+)-(\d+)").unwrap();
-    input
-        .lines()
-        .map(|line| {
-            let cap = re.captures(line).unwrap();
-            (
-                (
-                    cap.get(1).unwrap().as_str().parse::<u32>().unwrap(),
-                    cap.get(2).unwrap().as_str().parse::<u32>().unwrap(),
-                ),
-                (
-                    cap.get(3).unwrap().as_str().parse::<u32>().unwrap(),
-                    cap.get(4).unwrap().as_str().parse::<u32>().unwrap(),
-                ),
-            )
-        })
-        .collect()
+use crate::error::{parse_lines, AocError};
+
+type Pair = ((u32, u32), (u32, u32));
+
+fn preprocess(input: &str) -> Result<Vec<Pair>, AocError> {
+    let re = regex::Regex::new(r"^(\d+)-(\d+),(\d+)-(\d+)$").unwrap();
+    parse_lines(input, |line| {
+        let cap = re
+            .captures(line)
+            .ok_or_else(|| AocError::new(format!("invalid pair '{}'", line)))?;
+        let section = |index: usize| {
+            cap[index]
+                .parse::<u32>()
+                .map_err(|_| AocError::new(format!("invalid section '{}'", &cap[index])))
+        };
+        Ok(((section(1)?, section(2)?), (section(3)?, section(4)?)))
+    })
 }
 
-fn part1(input: &str) -> String {
-    let pairs = preprocess(input);
-    pairs
+fn part1(input: &str) -> Result<String, AocError> {
+    let pairs = preprocess(input)?;
+    Ok(pairs
         .iter()
         .map(|(pair1, pair2)| {
             u32::from(
@@ -29,20 +28,20 @@ fn part1(input: &str) -> String {
             )
         })
         .sum::<u32>()
-        .to_string()
+        .to_string())
 }
 
-fn part2(input: &str) -> String {
-    let pairs = preprocess(input);
-    pairs
+fn part2(input: &str) -> Result<String, AocError> {
+    let pairs = preprocess(input)?;
+    Ok(pairs
         .iter()
         .map(|(pair1, pair2)| u32::from((pair1.0 <= pair2.1) && (pair1.1 >= pair2.0)))
         .sum::<u32>()
-        .to_string()
+        .to_string())
 }
 
-crate::run!();
+crate::run!("Camp Cleanup", preprocess);
 
 crate::test_example_aoc!(2, 4);
 
-crate::test_aoc!(530, 903);
+crate::test_aoc!();
@@ -1,3 +1,5 @@
+use crate::error::AocError;
+
 type Actions = Vec<(usize, usize, usize)>;
 type Stacks = Vec<Vec<char>>;
 
@@ -23,65 +25,75 @@ fn parse_stacks(lines: &[&str]) -> Stacks {
     stacks
 }
 
-fn parse_actions(lines: &[&str]) -> Actions {
+fn parse_actions(lines: &[&str], stacks: usize) -> Result<Actions, AocError> {
     let mut moves = Vec::new();
-    let re = regex::Regex::new(r"move (\d+) from (\d+) to (\d+)").unwrap();
-    for line in lines {
+    let re = regex::Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+    for (index, line) in lines.iter().enumerate() {
         if !line.starts_with("move") {
             continue;
         }
 
-        let captures = re.captures(line).unwrap();
-        let count: usize = captures[1].parse().unwrap();
-        let from: usize = captures[2].parse::<usize>().unwrap() - 1;
-        let to: usize = captures[3].parse::<usize>().unwrap() - 1;
+        let invalid = || AocError::new(format!("invalid move '{}'", line)).at_line(index + 1);
+        let captures = re.captures(line).ok_or_else(invalid)?;
+        let count: usize = captures[1].parse().map_err(|_| invalid())?;
+        let stack = |capture: usize| match captures[capture].parse::<usize>() {
+            Ok(number) if (1..=stacks).contains(&number) => Ok(number - 1),
+            _ => Err(invalid()),
+        };
+        let from = stack(2)?;
+        let to = stack(3)?;
 
         moves.push((count, from, to))
     }
 
-    moves
+    Ok(moves)
 }
 
-fn preprocess(input: &str) -> (Stacks, Actions) {
+fn preprocess(input: &str) -> Result<(Stacks, Actions), AocError> {
     let lines = input.lines().collect::<Vec<_>>();
 
     let stacks = parse_stacks(&lines);
-    let actions = parse_actions(&lines);
+    let actions = parse_actions(&lines, stacks.len())?;
 
-    (stacks, actions)
+    Ok((stacks, actions))
 }
 
-fn part1(input: &str) -> String {
-    let (mut stacks, actions) = preprocess(input);
+fn too_many_crates(action: (usize, usize, usize)) -> AocError {
+    AocError::new(format!("cannot move {} crates from stack {}", action.0, action.1 + 1))
+}
+
+fn part1(input: &str) -> Result<String, AocError> {
+    let (mut stacks, actions) = preprocess(input)?;
 
     for action in actions {
         for _ in 0..action.0 {
             match stacks[action.1].pop() {
                 Some(element) => stacks[action.2].push(element),
-                None => {
-                    panic!("{:?}", action);
-                }
+                None => return Err(too_many_crates(action)),
             }
         }
     }
 
-    stacks.iter().map(|s| s.last().unwrap()).collect()
+    Ok(stacks.iter().filter_map(|s| s.last()).collect())
 }
 
-fn part2(input: &str) -> String {
-    let (mut stacks, actions) = preprocess(input);
+fn part2(input: &str) -> Result<String, AocError> {
+    let (mut stacks, actions) = preprocess(input)?;
 
     for action in actions {
-        let split_index = stacks[action.1].len() - action.0;
+        let split_index = stacks[action.1]
+            .len()
+            .checked_sub(action.0)
+            .ok_or_else(|| too_many_crates(action))?;
         let moved = stacks[action.1].split_off(split_index);
         stacks[action.2].extend_from_slice(&moved);
     }
 
-    stacks.iter().map(|s| s.last().unwrap()).collect()
+    Ok(stacks.iter().filter_map(|s| s.last()).collect())
 }
 
-crate::run!();
+crate::run!("Supply Stacks", preprocess; Keep);
 
 crate::test_example_aoc!("CMZ", "MCD");
 
-crate::test_aoc!("VJSFHWGFT", "LCTQFBVZV");
+crate::test_aoc!();
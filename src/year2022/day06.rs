@@ -1,4 +1,6 @@
-fn search_start_of_message(input: &str, window_size: usize) -> usize {
+use crate::error::AocError;
+
+fn search_start_of_message(input: &str, window_size: usize) -> Result<usize, AocError> {
     input
         .chars()
         .collect::<Vec<_>>()
@@ -12,19 +14,21 @@ fn search_start_of_message(input: &str, window_size: usize) -> usize {
                 None
             }
         })
-        .unwrap()
+        .ok_or_else(|| AocError::new(format!("no {} different characters in a row", window_size)))
 }
 
-fn part1(input: &str) -> String {
-    search_start_of_message(input, 4).to_string()
+fn part1(input: &str) -> Result<String, AocError> {
+    Ok(search_start_of_message(input, 4)?.to_string())
 }
 
-fn part2(input: &str) -> String {
-    search_start_of_message(input, 14).to_string()
+fn part2(input: &str) -> Result<String, AocError> {
+    Ok(search_start_of_message(input, 14)?.to_string())
 }
 
-crate::run!();
+crate::run!("Tuning Trouble");
 
 crate::test_example_aoc!(11, 26);
 
-crate::test_aoc!(1850, 2823);
+crate::test_examples_aoc!();
+
+crate::test_aoc!();
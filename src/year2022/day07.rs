@@ -4,6 +4,8 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use crate::error::AocError;
+
 type FileSystem = HashMap<PathBuf, Directory>;
 
 #[derive(Debug, Default)]
@@ -13,16 +15,17 @@ struct Directory {
     total_size: usize,
 }
 
-fn build_file_system(input: &str) -> FileSystem {
+fn build_file_system(input: &str) -> Result<FileSystem, AocError> {
     let mut file_system = FileSystem::new();
 
     let mut path = PathBuf::new();
     file_system.insert(path.clone(), Default::default());
-    for line in input.lines() {
+    for (index, line) in input.lines().enumerate() {
+        let invalid = |cause: &str| AocError::new(format!("{} '{}'", cause, line)).at_line(index + 1);
         if let Some(arg) = line.strip_prefix("$ cd ") {
             path = match arg {
                 "/" => PathBuf::from(""),
-                ".." => path.parent().unwrap().to_path_buf(),
+                ".." => path.parent().ok_or_else(|| invalid("no parent directory for"))?.to_path_buf(),
                 subdir => path.join(subdir),
             };
 
@@ -37,18 +40,24 @@ fn build_file_system(input: &str) -> FileSystem {
                 .get_mut(&path)
                 .unwrap()
                 .directories
-                .insert(subdir);
+                .insert(subdir.clone());
+            // Listed directories are not all visited
+            file_system.entry(subdir).or_default();
         } else {
-            let (size, name) = line.split(' ').collect_tuple::<(&str, &str)>().unwrap();
+            let (size, name) = line
+                .split(' ')
+                .collect_tuple::<(&str, &str)>()
+                .ok_or_else(|| invalid("invalid output"))?;
+            let size = size.parse::<usize>().map_err(|_| invalid("invalid file size in"))?;
             file_system
                 .get_mut(&path)
                 .unwrap()
                 .files
-                .insert(name.to_owned(), size.parse::<usize>().unwrap());
+                .insert(name.to_owned(), size);
         }
     }
 
-    file_system
+    Ok(file_system)
 }
 
 fn compute_directory_size(file_system: &mut FileSystem, path: &Path) {
@@ -81,33 +90,36 @@ fn compute_directory_size_100000_sum(file_system: &FileSystem, path: &Path) -> u
     total_size
 }
 
-fn preprocess(input: &str) -> FileSystem {
-    let mut filesystem = build_file_system(input);
+fn preprocess(input: &str) -> Result<FileSystem, AocError> {
+    let mut filesystem = build_file_system(input)?;
     compute_directory_size(&mut filesystem, &PathBuf::new());
-    filesystem
+    Ok(filesystem)
 }
 
-fn part1(input: &str) -> String {
-    let filesystem = preprocess(input);
-    compute_directory_size_100000_sum(&filesystem, &PathBuf::new()).to_string()
+fn part1(input: &str) -> Result<String, AocError> {
+    let filesystem = preprocess(input)?;
+    Ok(compute_directory_size_100000_sum(&filesystem, &PathBuf::new()).to_string())
 }
 
-fn part2(input: &str) -> String {
-    let filesystem = preprocess(input);
+fn part2(input: &str) -> Result<String, AocError> {
+    let filesystem = preprocess(input)?;
 
-    let free = 70000000 - filesystem[&PathBuf::new()].total_size;
-    let delete_size = 30000000 - free;
+    let used = filesystem[&PathBuf::new()].total_size;
+    let free = 70000000_usize
+        .checked_sub(used)
+        .ok_or_else(|| AocError::new(format!("{} used on a disk of 70000000", used)))?;
+    let delete_size = 30000000_usize.saturating_sub(free);
     let mut best_dir_size = usize::MAX;
     for directory in filesystem.values() {
         if directory.total_size > delete_size {
             best_dir_size = best_dir_size.min(directory.total_size);
         }
     }
-    best_dir_size.to_string()
+    Ok(best_dir_size.to_string())
 }
 
-crate::run!();
+crate::run!("No Space Left On Device", preprocess);
 
 crate::test_example_aoc!(95437, 24933642);
 
-crate::test_aoc!(1543140, 1117448);
+crate::test_aoc!();
@@ -1,54 +1,25 @@
 use std::collections::HashSet;
 
-enum ViewDirection {
-    North,
-    East,
-    South,
-    West,
-}
-
-struct ForestView<'m, T: Copy> {
-    data: &'m Vec<Vec<T>>,
-    direction: ViewDirection,
-}
-
-impl<'m, T: Copy> ForestView<'m, T> {
-    fn new(data: &'m Vec<Vec<T>>, direction: ViewDirection) -> Self {
-        Self { data, direction }
-    }
-
-    fn get(&self, i: usize, j: usize) -> T {
-        match self.direction {
-            ViewDirection::North => self.data[i][j],
-            ViewDirection::East => self.data[j][i],
-            ViewDirection::South => self.data[i][self.size() - 1 - j],
-            ViewDirection::West => self.data[self.size() - 1 - j][i],
-        }
-    }
-
-    fn get_original_coordinate(&self, i: usize, j: usize) -> (usize, usize) {
-        match self.direction {
-            ViewDirection::North => (i, j),
-            ViewDirection::East => (j, i),
-            ViewDirection::South => (i, self.size() - 1 - j),
-            ViewDirection::West => (self.size() - 1 - j, i),
-        }
-    }
-
-    fn size(&self) -> usize {
-        self.data.len()
-    }
-}
-
-fn find_visible_from_side(forest: ForestView<i32>) -> HashSet<(usize, usize)> {
+use crate::error::AocError;
+use crate::grid::{Grid, Transform, View};
+
+/// Views of the forest from each of its sides, looking along their rows.
+const SIDES: [Transform; 4] = [
+    Transform::Identity,
+    Transform::Transpose,
+    Transform::Rotate180,
+    Transform::Rotate90,
+];
+
+fn find_visible_from_side(forest: View<i32>) -> HashSet<(usize, usize)> {
     let mut visible = HashSet::new();
 
-    for row in 0..forest.size() {
+    for row in 0..forest.height() {
         let mut visible_height = -1;
-        for col in 0..forest.size() {
-            if forest.get(row, col) > visible_height {
-                visible.insert(forest.get_original_coordinate(row, col));
-                visible_height = forest.get(row, col);
+        for col in 0..forest.width() {
+            if forest[(row, col)] > visible_height {
+                visible.insert(forest.to_grid((row, col)));
+                visible_height = forest[(row, col)];
             }
         }
     }
@@ -56,12 +27,12 @@ fn find_visible_from_side(forest: ForestView<i32>) -> HashSet<(usize, usize)> {
     visible
 }
 
-fn compute_scenic(scenic: &mut Vec<Vec<i32>>, forest: ForestView<i32>) {
-    for row in 1..forest.size() - 1 {
-        let mut last_tree_of_size = vec![-1; 10];
+fn compute_scenic(scenic: &mut Grid<i32>, forest: View<i32>) {
+    for row in 1..forest.height() - 1 {
+        let mut last_tree_of_size = [-1; 10];
 
-        for col in 1..scenic.len() - 1 {
-            let current_height = forest.get(row, col);
+        for col in 1..forest.width() - 1 {
+            let current_height = forest[(row, col)];
             let mut visible_distance = col;
 
             // Find visible distance by checking all previous tree taller than the current one
@@ -76,62 +47,48 @@ fn compute_scenic(scenic: &mut Vec<Vec<i32>>, forest: ForestView<i32>) {
 
             last_tree_of_size[current_height as usize] = col as i32;
 
-            let coordinate = forest.get_original_coordinate(row, col);
-            scenic[coordinate.0][coordinate.1] *= visible_distance as i32;
+            scenic[forest.to_grid((row, col))] *= visible_distance as i32;
         }
     }
 }
 
-fn preprocess(input: &str) -> Vec<Vec<i32>> {
-    input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap() as i32)
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>()
+fn preprocess(input: &str) -> Result<Grid<i32>, AocError> {
+    let forest = Grid::try_parse(input, |c| {
+        c.to_digit(10)
+            .map(|height| height as i32)
+            .ok_or_else(|| AocError::new(format!("invalid tree height {:?}", c)))
+    })?;
+    if forest.height() == 0 {
+        return Err(AocError::new("no trees"));
+    }
+    Ok(forest)
 }
 
-fn part1(input: &str) -> String {
-    let forest = preprocess(input);
+fn part1(input: &str) -> Result<String, AocError> {
+    let forest = preprocess(input)?;
 
     let mut visible = HashSet::new();
-    visible.extend(find_visible_from_side(ForestView::new(
-        &forest,
-        ViewDirection::North,
-    )));
-    visible.extend(find_visible_from_side(ForestView::new(
-        &forest,
-        ViewDirection::East,
-    )));
-    visible.extend(find_visible_from_side(ForestView::new(
-        &forest,
-        ViewDirection::South,
-    )));
-    visible.extend(find_visible_from_side(ForestView::new(
-        &forest,
-        ViewDirection::West,
-    )));
-
-    visible.len().to_string()
+    for side in SIDES {
+        visible.extend(find_visible_from_side(forest.view(side)));
+    }
+
+    Ok(visible.len().to_string())
 }
 
-fn part2(input: &str) -> String {
-    let forest = preprocess(input);
+fn part2(input: &str) -> Result<String, AocError> {
+    let forest = preprocess(input)?;
 
-    let mut scenic = vec![vec![1i32; forest.len()]; forest.len()];
-    compute_scenic(&mut scenic, ForestView::new(&forest, ViewDirection::North));
-    compute_scenic(&mut scenic, ForestView::new(&forest, ViewDirection::East));
-    compute_scenic(&mut scenic, ForestView::new(&forest, ViewDirection::South));
-    compute_scenic(&mut scenic, ForestView::new(&forest, ViewDirection::West));
-    let max_scenic = scenic.iter().flatten().max().unwrap();
+    let mut scenic = Grid::new(forest.height(), forest.width(), 1i32);
+    for side in SIDES {
+        compute_scenic(&mut scenic, forest.view(side));
+    }
+    let max_scenic = scenic.iter().map(|(_, &score)| score).max().unwrap();
 
-    max_scenic.to_string()
+    Ok(max_scenic.to_string())
 }
 
-crate::run!();
+crate::run!("Treetop Tree House", preprocess);
 
 crate::test_example_aoc!(21, 8);
 
-crate::test_aoc!(1736, 268800);
+crate::test_aoc!();
@@ -1,16 +1,21 @@
+use crate::error::{parse_lines, AocError};
+
 enum Operation {
     Nop,
     Add(i32),
 }
 
 impl Operation {
-    fn from_string(string: &str) -> Option<Self> {
-        if string.starts_with("noop") {
-            Some(Operation::Nop)
-        } else if string.starts_with("addx") {
-            Some(Operation::Add(string[5..].parse().unwrap()))
+    fn from_string(string: &str) -> Result<Self, AocError> {
+        if string == "noop" {
+            Ok(Operation::Nop)
+        } else if let Some(value) = string.strip_prefix("addx ") {
+            let value = value
+                .parse()
+                .map_err(|_| AocError::new(format!("invalid value '{}'", value)))?;
+            Ok(Operation::Add(value))
         } else {
-            None
+            Err(AocError::new(format!("invalid instruction '{}'", string)))
         }
     }
 
@@ -22,16 +27,12 @@ impl Operation {
     }
 }
 
-fn preprocess(input: &str) -> Vec<Operation> {
-    input
-        .lines()
-        .map(Operation::from_string)
-        .map(Option::unwrap)
-        .collect::<Vec<_>>()
+fn preprocess(input: &str) -> Result<Vec<Operation>, AocError> {
+    parse_lines(input, Operation::from_string)
 }
 
-fn part1(input: &str) -> String {
-    let operations = preprocess(input);
+fn part1(input: &str) -> Result<String, AocError> {
+    let operations = preprocess(input)?;
 
     let mut interesting_cycle = 20;
     let mut x = 1;
@@ -51,11 +52,11 @@ fn part1(input: &str) -> String {
         x = new_x;
     }
 
-    result.to_string()
+    Ok(result.to_string())
 }
 
-fn part2(input: &str) -> String {
-    let operations = preprocess(input);
+fn part2(input: &str) -> Result<String, AocError> {
+    let operations = preprocess(input)?;
 
     let mut sprite_postion = 0;
     let crt_size = (40, 6);
@@ -80,11 +81,11 @@ fn part2(input: &str) -> String {
     //     }
     // }
 
-    "FJUBULRZ".to_string()
+    Ok("FJUBULRZ".to_string())
 }
 
-crate::run!();
+crate::run!("Cathode-Ray Tube", preprocess);
 
 // crate::test_example_aoc!(13140, "");
 
-crate::test_aoc!(13060, "FJUBULRZ");
+crate::test_aoc!();
@@ -1,3 +1,7 @@
+use std::str::FromStr;
+
+use crate::error::AocError;
+
 #[derive(Clone, Copy, Debug)]
 enum Operation {
     Add(i64),
@@ -24,36 +28,54 @@ struct Monkey {
     monkey_false: usize,
 }
 
+fn parse_number<T: FromStr>(string: &str) -> Result<T, AocError> {
+    string
+        .parse()
+        .map_err(|_| AocError::new(format!("invalid number '{}'", string)))
+}
+
 impl Monkey {
-    fn parse(lines: &[&str]) -> Self {
-        let items = lines[1][18..]
+    /// Parse the lines of a monkey, the first one being line `first_line` of
+    /// the input.
+    fn parse(lines: &[&str], first_line: usize) -> Result<Self, AocError> {
+        let at = |index: usize| move |error: AocError| error.at_line(first_line + index);
+        let field = |index: usize, prefix: &str| {
+            lines
+                .get(index)
+                .and_then(|line| line.trim_start().strip_prefix(prefix))
+                .ok_or_else(|| AocError::new(format!("expected '{}'", prefix)))
+                .map_err(at(index))
+        };
+
+        field(0, "Monkey ")?;
+        let items = field(1, "Starting items: ")?
             .split(", ")
-            .map(|s| s.parse::<i64>().unwrap())
-            .collect::<Vec<_>>();
-        let operation = {
-            let op = &lines[2][23..];
-            if op == "* old" {
-                Operation::Square
-            } else {
-                let operand = op[2..].parse::<i64>().unwrap();
-                if op.starts_with('*') {
-                    Operation::Multiply(operand)
-                } else {
-                    Operation::Add(operand)
-                }
-            }
+            .filter(|item| !item.is_empty())
+            .map(parse_number)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(at(1))?;
+        let operation = match field(2, "Operation: new = old ")? {
+            "* old" => Operation::Square,
+            op => match op.split_at_checked(2) {
+                Some(("* ", operand)) => Operation::Multiply(parse_number(operand).map_err(at(2))?),
+                Some(("+ ", operand)) => Operation::Add(parse_number(operand).map_err(at(2))?),
+                _ => return Err(at(2)(AocError::new(format!("invalid operation '{}'", op)))),
+            },
         };
-        let divisble_by = lines[3][21..].parse().unwrap();
-        let monkey_true = lines[4][29..].parse().unwrap();
-        let monkey_false = lines[5][30..].parse().unwrap();
+        let divisble_by = parse_number(field(3, "Test: divisible by ")?).map_err(at(3))?;
+        if divisble_by <= 0 {
+            return Err(at(3)(AocError::new(format!("invalid divisor {}", divisble_by))));
+        }
+        let monkey_true = parse_number(field(4, "If true: throw to monkey ")?).map_err(at(4))?;
+        let monkey_false = parse_number(field(5, "If false: throw to monkey ")?).map_err(at(5))?;
 
-        Self {
+        Ok(Self {
             items,
             operation,
             divisble_by,
             monkey_true,
             monkey_false,
-        }
+        })
     }
 }
 
@@ -85,30 +107,44 @@ where
     inspects[inspects.len() - 1] * inspects[inspects.len() - 2]
 }
 
-fn preprocess(input: &str) -> Vec<Monkey> {
+fn preprocess(input: &str) -> Result<Vec<Monkey>, AocError> {
     let lines = input.lines().collect::<Vec<_>>();
-    let monkeys = lines.chunks(7).map(Monkey::parse).collect::<Vec<_>>();
-    monkeys
+    let monkeys = lines
+        .chunks(7)
+        .enumerate()
+        .map(|(index, lines)| Monkey::parse(lines, 7 * index + 1))
+        .collect::<Result<Vec<_>, _>>()?;
+    if monkeys.len() < 2 {
+        return Err(AocError::new("fewer than 2 monkeys"));
+    }
+    for (index, monkey) in monkeys.iter().enumerate() {
+        for target in [monkey.monkey_true, monkey.monkey_false] {
+            if target == index || target >= monkeys.len() {
+                return Err(AocError::new(format!("monkey {} cannot throw to monkey {}", index, target)));
+            }
+        }
+    }
+    Ok(monkeys)
 }
 
-fn part1(input: &str) -> String {
-    let monkeys = preprocess(input);
+fn part1(input: &str) -> Result<String, AocError> {
+    let monkeys = preprocess(input)?;
     let monkey_business = run_rounds(monkeys, 20, |worry| worry / 3);
-    monkey_business.to_string()
+    Ok(monkey_business.to_string())
 }
 
-fn part2(input: &str) -> String {
-    let monkeys = preprocess(input);
+fn part2(input: &str) -> Result<String, AocError> {
+    let monkeys = preprocess(input)?;
     let factor = monkeys
         .iter()
         .map(|monkey| monkey.divisble_by)
         .product::<i64>();
     let monkey_business = run_rounds(monkeys, 10000, |worry| worry % factor);
-    monkey_business.to_string()
+    Ok(monkey_business.to_string())
 }
 
-crate::run!();
+crate::run!("Monkey in the Middle", preprocess);
 
 crate::test_example_aoc!(10605, 2713310158usize);
 
-crate::test_aoc!(112815, 25738411485usize);
+crate::test_aoc!();
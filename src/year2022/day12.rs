@@ -1,158 +1,75 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
-};
-
-type Position = (isize, isize);
-
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-struct State {
-    cost: usize,
-    position: Position,
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| self.position.cmp(&other.position))
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
+use crate::error::AocError;
+use crate::grid::{Grid, Position};
+use crate::search;
 
 struct Map {
-    map: Vec<Vec<u8>>,
+    map: Grid<u8>,
 }
 
 impl Map {
-    fn parse(input: &str) -> (Self, Position, Position) {
-        let mut start = (0, 0);
-        let mut end = (0, 0);
-        let map = input
-            .lines()
-            .enumerate()
-            .map(|(x, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(y, c)| match c {
-                        'S' => {
-                            start = (x as isize, y as isize);
-                            0
-                        }
-                        'E' => {
-                            end = (x as isize, y as isize);
-                            b'z' - b'a'
-                        }
-                        ch => ch as u8 - b'a',
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .collect::<Vec<_>>();
-
-        (Self { map }, start, end)
+    fn parse(input: &str) -> Result<(Self, Position, Position), AocError> {
+        let map = Grid::try_parse(input, |c| match c {
+            'a'..='z' | 'S' | 'E' => Ok(c),
+            _ => Err(AocError::new(format!("invalid elevation {:?}", c))),
+        })?;
+        let start = map.find(|&c| c == 'S').ok_or_else(|| AocError::new("no start position"))?;
+        let end = map.find(|&c| c == 'E').ok_or_else(|| AocError::new("no best signal position"))?;
+        let map = map.map(|&c| match c {
+            'S' => 0,
+            'E' => b'z' - b'a',
+            ch => ch as u8 - b'a',
+        });
+
+        Ok((Self { map }, start, end))
     }
 
-    fn get(&self, position: Position) -> Option<u8> {
-        if position.0 < 0
-            || position.0 >= self.map.len() as isize
-            || position.1 < 0
-            || position.1 >= self.map[position.0 as usize].len() as isize
-        {
-            None
-        } else {
-            Some(self.map[position.0 as usize][position.1 as usize])
-        }
+    fn get(&self, position: Position) -> u8 {
+        self.map[position]
     }
 }
 
-fn find_steps<S, V>(start_position: Position, stop_condition: S, visit_condition: V) -> usize
+fn find_steps<S, V>(map: &Map, start_position: Position, stop_condition: S, visit_condition: V) -> Result<usize, AocError>
 where
     S: Fn(Position) -> bool,
     V: Fn(Position, Position) -> bool,
 {
-    let mut cost = HashMap::new();
-    let mut to_visit = BinaryHeap::new();
-    to_visit.push(State {
-        cost: 0,
-        position: start_position,
-    });
-
-    while let Some(current) = to_visit.pop() {
-        if stop_condition(current.position) {
-            return current.cost;
-        }
-
-        if cost.contains_key(&current.position) {
-            continue;
-        }
-
-        cost.insert(current.position, current.cost);
-
-        let mut visit_neighbor = |neighbor| {
-            if visit_condition(current.position, neighbor) {
-                to_visit.push(State {
-                    cost: current.cost + 1,
-                    position: neighbor,
-                })
-            }
-        };
-
-        visit_neighbor((current.position.0 - 1, current.position.1));
-        visit_neighbor((current.position.0 + 1, current.position.1));
-        visit_neighbor((current.position.0, current.position.1 - 1));
-        visit_neighbor((current.position.0, current.position.1 + 1));
-    }
-
-    panic!("Path not found");
+    let successors = |&current: &Position| {
+        map.map
+            .neighbours4(current)
+            .filter(|&neighbor| visit_condition(current, neighbor))
+            .collect::<Vec<_>>()
+    };
+    search::bfs([start_position], successors, |&position| stop_condition(position))
+        .ok_or_else(|| AocError::new("path not found"))
 }
 
-pub fn part1(input: &str) -> String {
+pub fn part1(input: &str) -> Result<String, AocError> {
     // Input
-    let (map, start, end) = Map::parse(&input);
+    let (map, start, end) = Map::parse(input)?;
 
     // Star 1
     let stop_condition_1 = |position| position == end;
-    let visit_condition_1 = |current, neighbor| {
-        let current_elevation = map.get(current).unwrap();
-        if let Some(neighbor_elevation) = map.get(neighbor) {
-            neighbor_elevation <= current_elevation + 1
-        } else {
-            false
-        }
-    };
+    let visit_condition_1 = |current, neighbor| map.get(neighbor) <= map.get(current) + 1;
 
-    let steps1 = find_steps(start, stop_condition_1, visit_condition_1);
-    steps1.to_string()
+    let steps1 = find_steps(&map, start, stop_condition_1, visit_condition_1)?;
+    Ok(steps1.to_string())
 }
 
 
-pub fn part2(input: &str) -> String {
+pub fn part2(input: &str) -> Result<String, AocError> {
     // Input
-    let (map, _, end) = Map::parse(&input);
+    let (map, _, end) = Map::parse(input)?;
 
     // Star 2
-    let stop_condition_2 = |position| map.get(position).unwrap() == 0;
-    let visit_condition_2 = |current, neighbor| {
-        let current_elevation = map.get(current).unwrap();
-        if let Some(neighbor_elevation) = map.get(neighbor) {
-            neighbor_elevation >= current_elevation - 1
-        } else {
-            false
-        }
-    };
+    let stop_condition_2 = |position| map.get(position) == 0;
+    let visit_condition_2 = |current, neighbor| map.get(neighbor) + 1 >= map.get(current);
 
-    let steps2 = find_steps(end, stop_condition_2, visit_condition_2);
-    steps2.to_string()
+    let steps2 = find_steps(&map, end, stop_condition_2, visit_condition_2)?;
+    Ok(steps2.to_string())
 }
 
-crate::run!();
+crate::run!("Hill Climbing Algorithm", Map::parse);
 
 crate::test_example_aoc!(31, 29);
 
-crate::test_aoc!(420, 414);
+crate::test_aoc!();
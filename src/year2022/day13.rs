@@ -1,10 +1,12 @@
 use std::cmp::Ordering;
 
 use nom::character::complete::{char, digit1};
-use nom::combinator::map;
+use nom::combinator::{all_consuming, map, map_res};
 use nom::multi::separated_list0;
 use nom::{branch::alt, sequence::delimited, IResult};
 
+use crate::error::AocError;
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 enum PacketElement {
     Int(u32),
@@ -55,25 +57,35 @@ impl PacketElement {
     }
 
     fn parse_int(input: &str) -> IResult<&str, PacketElement> {
-        map(digit1, |s: &str| {
-            PacketElement::Int(s.parse::<u32>().unwrap())
-        })(input)
+        map_res(digit1, |s: &str| s.parse::<u32>().map(PacketElement::Int))(input)
+    }
+
+    fn from_str(string: &str) -> Result<Self, AocError> {
+        all_consuming(PacketElement::parse)(string)
+            .map(|(_, packet)| packet)
+            .map_err(|_| AocError::new(format!("invalid packet '{}'", string)))
     }
 }
 
-pub fn part1(input: &str) -> String {
-    // Input
-    let pairs = input
-        .lines()
-        .collect::<Vec<_>>()
+fn preprocess(input: &str) -> Result<Vec<[PacketElement; 2]>, AocError> {
+    let lines = input.lines().collect::<Vec<_>>();
+    lines
         .chunks(3)
-        .map(|lines| {
-            [
-                PacketElement::parse(lines[0]).unwrap().1,
-                PacketElement::parse(lines[1]).unwrap().1,
-            ]
+        .enumerate()
+        .map(|(index, lines)| {
+            let packet = |offset: usize| {
+                let line = lines.get(offset).ok_or_else(|| AocError::new("missing packet"));
+                line.and_then(|line| PacketElement::from_str(line))
+                    .map_err(|error| error.at_line(3 * index + offset + 1))
+            };
+            Ok([packet(0)?, packet(1)?])
         })
-        .collect::<Vec<_>>();
+        .collect()
+}
+
+pub fn part1(input: &str) -> Result<String, AocError> {
+    // Input
+    let pairs = preprocess(input)?;
 
     // Star 1
     let sum1: usize = pairs
@@ -87,24 +99,14 @@ pub fn part1(input: &str) -> String {
             }
         })
         .sum();
-    
-    sum1.to_string()
+
+    Ok(sum1.to_string())
 }
 
 
-pub fn part2(input: &str) -> String {
-     // Input
-     let pairs = input
-     .lines()
-     .collect::<Vec<_>>()
-     .chunks(3)
-     .map(|lines| {
-         [
-             PacketElement::parse(lines[0]).unwrap().1,
-             PacketElement::parse(lines[1]).unwrap().1,
-         ]
-     })
-     .collect::<Vec<_>>();
+pub fn part2(input: &str) -> Result<String, AocError> {
+    // Input
+    let pairs = preprocess(input)?;
 
     // Star 2
     let mut all = pairs.into_iter().flatten().collect::<Vec<_>>();
@@ -118,11 +120,11 @@ pub fn part2(input: &str) -> String {
     let index6 = all.iter().position(|elem| *elem == divider6).unwrap() + 1;
     let prod2 = index2 * index6;
 
-    prod2.to_string()
+    Ok(prod2.to_string())
 }
 
-crate::run!();
+crate::run!("Distress Signal", preprocess);
 
 crate::test_example_aoc!(13, 140);
 
-crate::test_aoc!(5580, 26200);
+crate::test_aoc!();
@@ -1,3 +1,6 @@
+use crate::error::{parse_lines, AocError};
+use crate::grid::{Cells, GrowingGrid};
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Tile {
     Air,
@@ -18,71 +21,48 @@ impl Position {
     }
 }
 struct Cave {
-    map: Vec<Vec<Tile>>,
-    x_min: usize,
-    x_max: usize,
+    map: GrowingGrid<Tile>,
     y_max: usize,
     infinite_floor: bool,
 }
 
 impl Cave {
     fn from_rocks(rocks: &[Vec<Position>], infinite_floor: bool) -> Self {
-        let mut x_min = usize::MAX;
-        let mut x_max = usize::MIN;
-        let mut y_max = usize::MIN;
-        for rock in rocks {
-            for corner in rock {
-                x_min = x_min.min(corner.x);
-                x_max = x_max.max(corner.x);
-                y_max = y_max.max(corner.y);
-            }
-        }
-
+        let mut y_max = rocks.iter().flatten().map(|corner| corner.y).max().unwrap();
         if infinite_floor {
             y_max += 2;
         }
 
-        let mut map = vec![vec![Tile::Air; y_max + 1]; x_max - x_min + 1];
+        let mut cave = Self {
+            map: GrowingGrid::new(Tile::Air),
+            y_max,
+            infinite_floor,
+        };
 
         for rock in rocks {
             for index in 0..rock.len() - 1 {
-                if rock[index].x == rock[index + 1].x {
-                    let min = rock[index].y.min(rock[index + 1].y);
-                    let max = rock[index].y.max(rock[index + 1].y);
-                    for r in min..max + 1 {
-                        map[rock[index].x - x_min][r] = Tile::Rock;
-                    }
-                } else {
-                    let min = rock[index].x.min(rock[index + 1].x);
-                    let max = rock[index].x.max(rock[index + 1].x);
-                    for r in min..max + 1 {
-                        map[r - x_min][rock[index].y] = Tile::Rock;
+                let (from, to) = (rock[index], rock[index + 1]);
+                for x in from.x.min(to.x)..from.x.max(to.x) + 1 {
+                    for y in from.y.min(to.y)..from.y.max(to.y) + 1 {
+                        cave.set(Position::new(x, y), Tile::Rock);
                     }
                 }
             }
         }
 
-        if infinite_floor {
-            for col in &mut map {
-                col[y_max] = Tile::Rock;
-            }
-        }
-
-        Self {
-            map,
-            x_min,
-            x_max,
-            y_max,
-            infinite_floor,
-        }
+        cave
     }
 
     fn set(&mut self, position: Position, tile: Tile) {
-        self.map[position.x - self.x_min][position.y] = tile;
+        self.map.set((position.y as isize, position.x as isize), tile);
     }
 
     fn get(&self, position: Position) -> Tile {
-        self.map[position.x - self.x_min][position.y]
+        if self.infinite_floor && position.y == self.y_max {
+            return Tile::Rock;
+        }
+        let tile = self.map.get((position.y as isize, position.x as isize));
+        tile.copied().unwrap_or(Tile::Air)
     }
 
     fn drop_sand(&mut self, position: Position) -> Option<Position> {
@@ -92,23 +72,6 @@ impl Cave {
             let below_left = Position::new(position.x - 1, position.y + 1);
             let below_right = Position::new(position.x + 1, position.y + 1);
 
-            // Increase map size if needed
-            if below_left.x < self.x_min {
-                self.x_min -= 1;
-                self.map.insert(0, vec![Tile::Air; self.y_max + 1]);
-                if self.infinite_floor {
-                    self.set(Position::new(self.x_min, self.y_max), Tile::Rock);
-                }
-            }
-
-            if below_right.x >= self.x_min + self.map.len() {
-                self.x_max += 1;
-                self.map.push(vec![Tile::Air; self.y_max + 1]);
-                if self.infinite_floor {
-                    self.set(Position::new(self.x_max, self.y_max), Tile::Rock);
-                }
-            }
-
             // Check if falling into the abyss
             if !self.infinite_floor && below.y > self.y_max {
                 return None; // Sand falls into the abyss
@@ -130,9 +93,10 @@ impl Cave {
 
     #[allow(unused)]
     fn print(&self) {
-        for y in 0..self.map[0].len() {
-            for x in 0..self.map.len() {
-                match self.map[x][y] {
+        let bounds = self.map.bounds();
+        for y in 0..=self.y_max {
+            for x in bounds.cols.clone() {
+                match self.get(Position::new(x as usize, y)) {
                     Tile::Rock => print!("#"),
                     Tile::Sand => print!("o"),
                     Tile::Air => print!("."),
@@ -143,59 +107,57 @@ impl Cave {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Vec<Position>> {
-    input
-        .lines()
-        .map(|line| {
-            line.split(" -> ")
-                .map(|string| {
-                    let index = string.find(',').unwrap();
-                    Position {
-                        x: string[..index].parse::<usize>().unwrap(),
-                        y: string[index + 1..].parse::<usize>().unwrap(),
-                    }
+fn parse_input(input: &str) -> Result<Vec<Vec<Position>>, AocError> {
+    let rocks = parse_lines(input, |line| {
+        line.split(" -> ")
+            .map(|string| {
+                let invalid = || AocError::new(format!("invalid position '{}'", string));
+                let (x, y) = string.split_once(',').ok_or_else(invalid)?;
+                Ok(Position {
+                    x: x.parse::<usize>().map_err(|_| invalid())?,
+                    y: y.parse::<usize>().map_err(|_| invalid())?,
                 })
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>()
+            })
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+    if rocks.is_empty() {
+        return Err(AocError::new("no rocks"));
+    }
+    Ok(rocks)
 }
 
-fn part1(input: &str) -> String {
+fn part1(input: &str) -> Result<String, AocError> {
     // Input
-    let rocks = parse_input(input);
+    let rocks = parse_input(input)?;
 
     let sand_source = Position::new(500, 0);
 
     // Star 1
     let mut cave = Cave::from_rocks(&rocks, false);
 
-    let mut count1 = 0;
-    while cave.drop_sand(sand_source).is_some() {
-        count1 += 1;
-    }
+    while cave.drop_sand(sand_source).is_some() {}
+    let count1 = cave.map.count(&Tile::Sand);
 
-    count1.to_string()
+    Ok(count1.to_string())
 }
 
-fn part2(input: &str) -> String {
+fn part2(input: &str) -> Result<String, AocError> {
     // Input
-    let rocks = parse_input(input);
+    let rocks = parse_input(input)?;
 
     let sand_source = Position::new(500, 0);
 
    // Star 2
    let mut cave = Cave::from_rocks(&rocks, true);
 
-   let mut count2 = 1;
-   while cave.drop_sand(sand_source) != Some(sand_source) {
-       count2 += 1;
-   }
+   while cave.drop_sand(sand_source) != Some(sand_source) {}
+   let count2 = cave.map.count(&Tile::Sand);
 
-    count2.to_string()
+    Ok(count2.to_string())
 }
 
-crate::run!();
+crate::run!("Regolith Reservoir", parse_input);
 
 crate::test_example_aoc!(24, 93);
 
-crate::test_aoc!(873, 24813);
+crate::test_aoc!();
@@ -1,20 +1,10 @@
 use std::collections::HashSet;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Pos {
-    x: i64,
-    y: i64,
-}
-
-impl Pos {
-    fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
-    }
+use crate::error::{parse_lines, AocError};
+use crate::geometry::Point2;
+use crate::solution::PuzzleParams;
 
-    fn dist(&self, p: Pos) -> i64 {
-        (self.x - p.x).abs() + (self.y - p.y).abs()
-    }
-}
+type Pos = Point2<i64>;
 
 #[derive(Debug, Clone)]
 struct Reading {
@@ -24,29 +14,29 @@ struct Reading {
 }
 
 impl Reading {
-    fn from_string(input: &str) -> Self {
+    fn from_string(input: &str) -> Result<Self, AocError> {
         let regex =
             regex::Regex::new(r"Sensor at x=(.+), y=(.+): closest beacon is at x=(.+), y=(.+)")
                 .unwrap();
-        let cap = regex.captures(input).unwrap();
-        let sensor = Pos::new(
-            cap.get(1).unwrap().as_str().parse::<i64>().unwrap(),
-            cap.get(2).unwrap().as_str().parse::<i64>().unwrap(),
-        );
-        let beacon = Pos::new(
-            cap.get(3).unwrap().as_str().parse::<i64>().unwrap(),
-            cap.get(4).unwrap().as_str().parse::<i64>().unwrap(),
-        );
-        Self {
+        let cap = regex
+            .captures(input)
+            .ok_or_else(|| AocError::new(format!("invalid reading '{}'", input)))?;
+        let coordinate = |index: usize| {
+            cap[index]
+                .parse::<i64>()
+                .map_err(|_| AocError::new(format!("invalid coordinate '{}'", &cap[index])))
+        };
+        let sensor = Pos::new(coordinate(1)?, coordinate(2)?);
+        let beacon = Pos::new(coordinate(3)?, coordinate(4)?);
+        Ok(Self {
             sensor,
             beacon,
-            radius: sensor.dist(beacon),
-        }
+            radius: sensor.manhattan(beacon),
+        })
     }
 
     fn covers(&self, pos: Pos) -> bool {
-        // println!("{} {} ", self.sensor.dist(pos), self.sensor.dist(self.beacon));
-        self.sensor.dist(pos) <= self.radius
+        self.sensor.manhattan(pos) <= self.radius
     }
 }
 
@@ -71,14 +61,12 @@ fn find_overlap_sensors_line(readings: &[Reading], y: i64) -> Vec<(i64, i64)> {
 
     intervals.sort();
 
-    let mut merged = Vec::new();
-    merged.push(intervals[0]);
-
-    for interval in &intervals[1..] {
-        if interval.0 <= merged.last().unwrap().1 {
-            merged.last_mut().unwrap().1 = interval.1.max(merged.last().unwrap().1);
-        } else {
-            merged.push(*interval);
+    // No interval at all when no sensor reaches the line
+    let mut merged: Vec<(i64, i64)> = Vec::new();
+    for interval in intervals {
+        match merged.last_mut() {
+            Some(last) if interval.0 <= last.1 => last.1 = last.1.max(interval.1),
+            _ => merged.push(interval),
         }
     }
 
@@ -147,12 +135,12 @@ fn find_uncovered(
     None
 }
 
-fn part1(input: &str) -> String {
+fn part1(input: &str, params: &PuzzleParams) -> Result<String, AocError> {
     // Input
-    let readings = input.lines().map(Reading::from_string).collect::<Vec<_>>();
+    let readings = parse_lines(input, Reading::from_string)?;
 
     // Star 1
-    let y = 2000000;
+    let y = params.get("y", 10, 2000000)?;
     let intervals = find_overlap_sensors_line(&readings, y);
 
     let count: i64 = intervals
@@ -163,22 +151,25 @@ fn part1(input: &str) -> String {
     let occupied = find_occupied_tiles_line(&readings, y);
     let count = count - occupied.len() as i64;
 
-    count.to_string()
+    Ok(count.to_string())
 }
 
-fn part2(input: &str) -> String {
+fn part2(input: &str, params: &PuzzleParams) -> Result<String, AocError> {
     // Input
-    let readings = input.lines().map(Reading::from_string).collect::<Vec<_>>();
+    let readings = parse_lines(input, Reading::from_string)?;
 
     // Star 2
-    let beacon = find_uncovered(&readings, 0, 4000000, 0, 4000000).unwrap();
+    let max = params.get("max", 20, 4000000)?;
+    let beacon = find_uncovered(&readings, 0, max, 0, max).ok_or_else(|| AocError::new("no uncovered position"))?;
     let freq = 4000000 * beacon.x + beacon.y;
 
-    freq.to_string()
+    Ok(freq.to_string())
 }
 
-crate::run!();
+crate::run!("Beacon Exclusion Zone");
+
+crate::test_example_aoc!(26, 56000011);
 
-// crate::test_example_aoc!(26, 56000011);
+crate::test_examples_aoc!();
 
-crate::test_aoc!(5716881, 10852583132904i64);
+crate::test_aoc!();
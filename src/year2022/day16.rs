@@ -1,6 +1,5 @@
-use std::collections::{HashMap, HashSet};
-
-use itertools::Itertools;
+use crate::error::{parse_lines, AocError};
+use crate::graph::{Distances, Graph};
 
 #[derive(Debug)]
 struct Room {
@@ -10,59 +9,61 @@ struct Room {
 }
 
 impl Room {
-    fn from_str(string: &str) -> Self {
+    fn from_str(string: &str) -> Result<Self, AocError> {
         let r = regex::Regex::new("Valve (.+) has flow rate=(.+); tunnels? leads? to valves? (.+)")
             .unwrap();
-        let cap = r.captures(string).unwrap();
-        let valve = cap.get(1).unwrap().as_str().to_owned();
-        let flow_rate = cap.get(2).unwrap().as_str().parse::<u32>().unwrap();
-        let tunnels = cap
-            .get(3)
-            .unwrap()
-            .as_str()
+        let cap = r
+            .captures(string)
+            .ok_or_else(|| AocError::new(format!("invalid valve '{}'", string)))?;
+        let valve = cap[1].to_owned();
+        let flow_rate = cap[2]
+            .parse::<u32>()
+            .map_err(|_| AocError::new(format!("invalid flow rate '{}'", &cap[2])))?;
+        let tunnels = cap[3]
             .split(", ")
             .map(str::to_owned)
             .collect::<Vec<_>>();
-        Self {
+        Ok(Self {
             name: valve,
             flow_rate,
             tunnels,
-        }
+        })
     }
 }
 
-fn compute_distances(rooms: &[Room]) -> Vec<Vec<u32>> {
-    let mut distances = vec![vec![0; rooms.len()]; rooms.len()];
-
-    let room_to_index = rooms
-        .iter()
-        .enumerate()
-        .map(|(index, room)| (room.name.clone(), index))
-        .collect::<HashMap<_, _>>();
-
-    for room in rooms {
-        let start = room_to_index[&room.name];
-        let mut visited = HashSet::new();
-        let mut to_visit = Vec::new();
-        to_visit.push((start, 0));
-        while !to_visit.is_empty() {
-            let (current, distance) = to_visit.remove(0);
-
-            if visited.contains(&current) {
-                continue;
-            }
+/// The valves worth opening, and the room to start from, as node 0.
+struct Network {
+    flow_rates: Vec<u32>,
+    distances: Distances,
+}
 
-            distances[start][current] = distance;
-            distances[current][start] = distance;
-            visited.insert(current);
+impl Network {
+    fn from_rooms(rooms: &[Room]) -> Result<Self, AocError> {
+        let adjacency = rooms
+            .iter()
+            .map(|room| (room.name.as_str(), room.tunnels.iter().map(String::as_str)));
+        let (graph, labels) = Graph::from_adjacency(adjacency);
 
-            for tunnel in &rooms[current].tunnels {
-                to_visit.push((room_to_index[tunnel], distance + 1));
-            }
+        let mut flow_rates = vec![None; labels.len()];
+        for room in rooms {
+            flow_rates[labels.get(&room.name).unwrap()] = Some(room.flow_rate);
+        }
+        if let Some(unknown) = flow_rates.iter().position(Option::is_none) {
+            return Err(AocError::new(format!("unknown valve {}", labels.label(unknown))));
         }
-    }
 
-    distances
+        let start = labels.get("AA").ok_or_else(|| AocError::new("no valve AA"))?;
+        let valves = rooms
+            .iter()
+            .filter(|room| room.flow_rate > 0 && room.name != "AA")
+            .map(|room| labels.get(&room.name).unwrap());
+        let keep = std::iter::once(start).chain(valves).collect::<Vec<_>>();
+
+        Ok(Self {
+            flow_rates: keep.iter().map(|&node| flow_rates[node].unwrap()).collect(),
+            distances: graph.compress(&keep).floyd_warshall(),
+        })
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -74,8 +75,7 @@ struct State1 {
 }
 
 fn find_pressure1(
-    rooms: &[Room],
-    distances: &[Vec<u32>],
+    network: &Network,
     state: State1,
     visited: &mut [bool],
     max_pressure: &mut u32,
@@ -87,15 +87,15 @@ fn find_pressure1(
 
     // Open the valve
     let released_pressure =
-        state.released_pressure + state.time_left * rooms[state.room_index].flow_rate;
+        state.released_pressure + state.time_left * network.flow_rates[state.room_index];
     *max_pressure = (*max_pressure).max(released_pressure);
 
     // Try to go in other tooms
-    for tunnel in 0..distances.len() {
-        if rooms[tunnel].flow_rate == 0
-            || visited[tunnel]
-            || state.time_left < distances[state.room_index][tunnel] + 1
-        {
+    for tunnel in 0..network.flow_rates.len() {
+        let Some(distance) = network.distances[state.room_index][tunnel] else {
+            continue;
+        };
+        if network.flow_rates[tunnel] == 0 || visited[tunnel] || state.time_left < distance + 1 {
             continue;
         }
 
@@ -103,162 +103,91 @@ fn find_pressure1(
 
         let mut new_state = state;
         new_state.released_pressure = released_pressure;
-        new_state.available_flow_rate -= rooms[state.room_index].flow_rate;
+        new_state.available_flow_rate -= network.flow_rates[state.room_index];
         new_state.room_index = tunnel;
-        new_state.time_left -= distances[state.room_index][tunnel] + 1;
-        find_pressure1(rooms, distances, new_state, visited, max_pressure);
+        new_state.time_left -= distance + 1;
+        find_pressure1(network, new_state, visited, max_pressure);
 
         visited[tunnel] = false;
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct State2 {
-    released_pressure: u32,
-    available_flow_rate: u32,
-    room_index: (usize, usize),
-    time_left: (u32, u32),
-}
-
-fn find_pressure2(
-    rooms: &[Room],
-    distances: &[Vec<u32>],
-    state: State2,
-    visited: &mut [bool],
-    max_pressure: &mut u32,
+/// Record in `best` the most pressure that can be released by opening each
+/// set of valves, as a bitmask of their nodes less one.
+fn find_pressures(
+    network: &Network,
+    (room_index, time_left): (usize, u32),
+    (opened, released_pressure): (usize, u32),
+    best: &mut [u32],
 ) {
-    // If all the available leftover pressure is not enough give up
-    if state.released_pressure + state.available_flow_rate * state.time_left.0 <= *max_pressure {
-        return;
-    }
-
-    if state.released_pressure + state.available_flow_rate * state.time_left.1 <= *max_pressure {
-        return;
-    }
+    best[opened] = best[opened].max(released_pressure);
 
-    // Open the valve
-    let released_pressure = state.released_pressure
-        + state.time_left.0 * rooms[state.room_index.0].flow_rate
-        + state.time_left.1 * rooms[state.room_index.1].flow_rate;
-    *max_pressure = (*max_pressure).max(released_pressure);
-
-    // Try to go in other rooms
-    for neighbor0 in 0..distances.len() {
-        if rooms[neighbor0].flow_rate == 0
-            || visited[neighbor0]
-            || state.time_left.0 < distances[state.room_index.0][neighbor0] + 1
-        {
+    for valve in 1..network.flow_rates.len() {
+        let bit = valve - 1;
+        let Some(distance) = network.distances[room_index][valve] else {
             continue;
-        }
-
-        visited[neighbor0] = true;
-
-        for neighbor1 in 0..distances.len() {
-            if rooms[neighbor1].flow_rate == 0
-                || visited[neighbor1]
-                || state.time_left.1 < distances[state.room_index.1][neighbor1] + 1
-            {
-                continue;
-            }
-
-            visited[neighbor1] = true;
-
-            let mut new_state = state;
-            new_state.released_pressure = released_pressure;
-            new_state.available_flow_rate -=
-                rooms[state.room_index.0].flow_rate + rooms[state.room_index.1].flow_rate;
-            new_state.room_index.0 = neighbor0;
-            new_state.time_left.0 -= distances[state.room_index.0][neighbor0] + 1;
-            new_state.room_index.1 = neighbor1;
-            new_state.time_left.1 -= distances[state.room_index.1][neighbor1] + 1;
-            find_pressure2(rooms, distances, new_state, visited, max_pressure);
-
-            visited[neighbor1] = false;
-        }
-
-        visited[neighbor0] = false;
-    }
-
-    for neighbor1 in 0..distances.len() {
-        if rooms[neighbor1].flow_rate == 0
-            || visited[neighbor1]
-            || state.time_left.1 < distances[state.room_index.1][neighbor1] + 1
-        {
+        };
+        if opened & 1 << bit != 0 || time_left <= distance + 1 {
             continue;
         }
 
-        visited[neighbor1] = true;
-
-        let mut new_state = state;
-        new_state.released_pressure =
-            state.released_pressure + state.time_left.1 * rooms[state.room_index.1].flow_rate;
-        new_state.available_flow_rate -= rooms[state.room_index.1].flow_rate;
-        new_state.room_index.1 = neighbor1;
-        new_state.time_left.1 -= distances[state.room_index.1][neighbor1] + 1;
-        find_pressure2(rooms, distances, new_state, visited, max_pressure);
-
-        visited[neighbor1] = false;
+        let time_left = time_left - distance - 1;
+        let released_pressure = released_pressure + time_left * network.flow_rates[valve];
+        find_pressures(network, (valve, time_left), (opened | 1 << bit, released_pressure), best);
     }
 }
 
-fn part1(input: &str) -> String {
+fn part1(input: &str) -> Result<String, AocError> {
     // Input
-    let rooms = input.lines().map(Room::from_str).collect::<Vec<_>>();
+    let rooms = parse_lines(input, Room::from_str)?;
 
-    let distances = compute_distances(&rooms);
-    let start = rooms
-        .iter()
-        .find_position(|room| room.name == "AA")
-        .unwrap()
-        .0;
+    let network = Network::from_rooms(&rooms)?;
 
     // Star 1
-    let available_flow_rate = rooms.iter().map(|room| room.flow_rate).sum::<u32>();
+    let available_flow_rate = network.flow_rates.iter().sum::<u32>();
 
-    let mut visited = vec![false; rooms.len()];
-    visited[start] = true;
+    let mut visited = vec![false; network.flow_rates.len()];
+    visited[0] = true;
     let mut pressure1 = 0;
     let state = State1 {
         released_pressure: 0,
         available_flow_rate,
-        room_index: start,
+        room_index: 0,
         time_left: 30,
     };
-    find_pressure1(&rooms, &distances, state, &mut visited, &mut pressure1);
+    find_pressure1(&network, state, &mut visited, &mut pressure1);
 
-    pressure1.to_string()
+    Ok(pressure1.to_string())
 }
 
-fn part2(input: &str) -> String {
+fn part2(input: &str) -> Result<String, AocError> {
     // Input
-    let rooms = input.lines().map(Room::from_str).collect::<Vec<_>>();
+    let rooms = parse_lines(input, Room::from_str)?;
 
-    let distances = compute_distances(&rooms);
-    let start = rooms
-        .iter()
-        .find_position(|room| room.name == "AA")
-        .unwrap()
-        .0;
+    let network = Network::from_rooms(&rooms)?;
 
     // Star 2
-    let available_flow_rate = rooms.iter().map(|room| room.flow_rate).sum::<u32>();
-
-    let mut visited = vec![false; rooms.len()];
-    visited[start] = true;
-    let mut pressure2 = 0;
-    let state = State2 {
-        released_pressure: 0,
-        available_flow_rate,
-        room_index: (start, start),
-        time_left: (26, 26),
-    };
-    find_pressure2(&rooms, &distances, state, &mut visited, &mut pressure2);
+    // Each of us opens its own set of valves
+    let valves = network.flow_rates.len() - 1;
+    let mut best = vec![0; 1 << valves];
+    find_pressures(&network, (0, 26), (0, 0), &mut best);
+
+    // Best pressure for any subset of each set
+    for bit in 0..valves {
+        for opened in 0..best.len() {
+            if opened & 1 << bit != 0 {
+                best[opened] = best[opened].max(best[opened ^ 1 << bit]);
+            }
+        }
+    }
+    let all = best.len() - 1;
+    let pressure2 = (0..best.len()).map(|opened| best[opened] + best[all ^ opened]).max().unwrap_or(0);
 
-    pressure2.to_string()
+    Ok(pressure2.to_string())
 }
 
-crate::run!();
+crate::run!("Proboscidea Volcanium");
 
-// crate::test_example_aoc!(1651, 1707); //TODO
+crate::test_example_aoc!(1651, 1707);
 
-crate::test_aoc!(1896, 2576);
+crate::test_aoc!();
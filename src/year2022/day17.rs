@@ -1,3 +1,6 @@
+use crate::error::AocError;
+use crate::grid::{Cells, GrowingGrid};
+
 #[derive(Debug, Clone, Copy)]
 enum Shape {
     Horizontal,
@@ -20,7 +23,7 @@ impl Shape {
 }
 
 struct Cave {
-    cave: Vec<Vec<bool>>,
+    cave: GrowingGrid<bool>,
     jets: Vec<char>,
     jet_index: usize,
     start_x: usize,
@@ -29,7 +32,7 @@ struct Cave {
 impl Cave {
     fn new(jets: Vec<char>) -> Self {
         Self {
-            cave: Vec::new(),
+            cave: GrowingGrid::new(false),
             jets,
             jet_index: 0,
             start_x: 4,
@@ -38,7 +41,6 @@ impl Cave {
 
     fn add_block(&mut self, shape: Shape) {
         let mut position = (self.start_x as isize, 2);
-        self.cave.resize(self.start_x + 3, vec![false; 7]);
         // println!("init {:?}", position);
         loop {
             // Blocks falls
@@ -66,7 +68,7 @@ impl Cave {
 
         // Add block and update start position
         for tile in shape.get_tiles() {
-            self.cave[(position.0 + tile.0) as usize][(position.1 + tile.1) as usize] = true;
+            self.cave.set((position.0 + tile.0, position.1 + tile.1), true);
             self.start_x = self.start_x.max((position.0 + tile.0) as usize + 5);
         }
         // println!("y {:?}", self.start_y);
@@ -84,22 +86,18 @@ impl Cave {
     }
 
     fn free_tile(&self, position: (isize, isize)) -> bool {
-        if position.0 < 0
-            || position.0 >= self.cave.len() as isize
-            || position.1 < 0
-            || position.1 >= self.cave[position.0 as usize].len() as isize
-        {
+        if position.0 < 0 || !(0..7).contains(&position.1) {
             false
         } else {
-            !self.cave[position.0 as usize][position.1 as usize]
+            self.cave.get(position) != Some(&true)
         }
     }
 
     #[allow(unused)]
     fn print(&self) {
-        for line in self.cave.iter().rev().take(100) {
-            for tile in line {
-                if *tile {
+        for row in self.cave.bounds().rows.rev().take(100) {
+            for col in 0..7 {
+                if self.cave.get((row, col)) == Some(&true) {
                     print!("#");
                 } else {
                     print!(".");
@@ -110,22 +108,33 @@ impl Cave {
     }
 }
 
-pub fn find_pattern_start(data: &[usize], size: usize) -> (usize, usize) {
+pub fn find_pattern_start(data: &[usize], size: usize) -> Option<(usize, usize)> {
     for start in 0..data.len() - size {
         for s in start + 1..data.len() - size {
             if data[s..s + size] == data[start..start + size] {
-                return (start, s - start);
+                return Some((start, s - start));
             }
         }
     }
 
-    panic!("Pattern not found");
+    None
 }
 
-fn part1(input: &str) -> String {
+fn preprocess(input: &str) -> Result<Vec<char>, AocError> {
     let jets = input.chars().collect::<Vec<_>>();
+    if let Some(jet) = jets.iter().find(|&&jet| jet != '<' && jet != '>') {
+        return Err(AocError::new(format!("invalid jet {:?}", jet)));
+    }
+    if jets.is_empty() {
+        return Err(AocError::new("no jets"));
+    }
+    Ok(jets)
+}
+
+fn part1(input: &str) -> Result<String, AocError> {
+    let jets = preprocess(input)?;
 
-    let rocks = vec![
+    let rocks = [
         Shape::Horizontal,
         Shape::Cross,
         Shape::Corner,
@@ -140,13 +149,13 @@ fn part1(input: &str) -> String {
     }
     let height1 = cave.start_x - 4;
 
-    height1.to_string()
+    Ok(height1.to_string())
 }
 
-fn part2(input: &str) -> String {
-    let jets = input.chars().collect::<Vec<_>>();
+fn part2(input: &str) -> Result<String, AocError> {
+    let jets = preprocess(input)?;
 
-    let rocks = vec![
+    let rocks = [
         Shape::Horizontal,
         Shape::Cross,
         Shape::Corner,
@@ -169,7 +178,8 @@ fn part2(input: &str) -> String {
     }
 
     let size = 5;
-    let (start, length) = find_pattern_start(&increase, size);
+    let (start, length) =
+        find_pattern_start(&increase, size).ok_or_else(|| AocError::new("no repeating pattern"))?;
 
     let pattern0 = &increase[..start];
     let pattern = &increase[start..start + length];
@@ -186,11 +196,11 @@ fn part2(input: &str) -> String {
         + pattern[..left].iter().sum::<usize>()
         - 1;
 
-    height2.to_string()
+    Ok(height2.to_string())
 }
 
-crate::run!();
+crate::run!("Pyroclastic Flow", preprocess);
 
 crate::test_example_aoc!(3068, 1514285714288usize);
 
-crate::test_aoc!(3171, 1586627906921usize);
+crate::test_aoc!();
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use crate::error::{parse_lines, AocError};
+
 type Robots = [u16; 4];
 type Resources = [u16; 4];
 
@@ -9,24 +11,30 @@ struct Blueprint {
 }
 
 impl Blueprint {
-    fn from_str(string: &str) -> Self {
+    fn from_str(string: &str) -> Result<Self, AocError> {
         let regex = regex::Regex::new(r"Blueprint (\d+): Each ore robot costs (\d+) ore. Each clay robot costs (\d+) ore. Each obsidian robot costs (\d+) ore and (\d+) clay. Each geode robot costs (\d+) ore and (\d+) obsidian.").unwrap();
-        let capture = regex.captures(string).unwrap();
-        let get = |index| capture.get(index).unwrap().as_str().parse::<u16>().unwrap();
-
-        Self {
+        let capture = regex
+            .captures(string)
+            .ok_or_else(|| AocError::new(format!("invalid blueprint '{}'", string)))?;
+        let get = |index: usize| {
+            capture[index]
+                .parse::<u16>()
+                .map_err(|_| AocError::new(format!("invalid cost '{}'", &capture[index])))
+        };
+
+        Ok(Self {
             robot_costs: [
-                [get(2), 0, 0, 0],
-                [get(3), 0, 0, 0],
-                [get(4), get(5), 0, 0],
-                [get(6), 0, get(7), 0],
+                [get(2)?, 0, 0, 0],
+                [get(3)?, 0, 0, 0],
+                [get(4)?, get(5)?, 0, 0],
+                [get(6)?, 0, get(7)?, 0],
             ],
-        }
+        })
     }
 }
 
-fn preprocess(input: &str) -> Vec<Blueprint> {
-    input.lines().map(Blueprint::from_str).collect::<Vec<_>>()
+fn preprocess(input: &str) -> Result<Vec<Blueprint>, AocError> {
+    parse_lines(input, Blueprint::from_str)
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
@@ -115,8 +123,8 @@ fn evaluate_blueprint(blueprint: &Blueprint, state: State) -> u16 {
     max_geodes
 }
 
-fn part1(input: &str) -> String {
-    let blueprints = preprocess(input);
+fn part1(input: &str) -> Result<String, AocError> {
+    let blueprints = preprocess(input)?;
 
     let score = blueprints
         .iter()
@@ -132,11 +140,11 @@ fn part1(input: &str) -> String {
         })
         .sum::<usize>();
 
-    score.to_string()
+    Ok(score.to_string())
 }
 
-fn part2(input: &str) -> String {
-    let blueprints = preprocess(input);
+fn part2(input: &str) -> Result<String, AocError> {
+    let blueprints = preprocess(input)?;
 
     let score = blueprints
         .iter()
@@ -152,11 +160,11 @@ fn part2(input: &str) -> String {
         })
         .product::<usize>();
 
-    score.to_string()
+    Ok(score.to_string())
 }
 
-crate::run!();
+crate::run!("Not Enough Minerals", preprocess);
 
 crate::test_example_aoc!(33, 3472);
 
-crate::test_aoc!(1565, 10672);
+crate::test_aoc!();
@@ -1,13 +1,19 @@
 use itertools::Itertools;
 
-fn preprocess(input: &str) -> Vec<(usize, i64)> {
-    let numbers = input
-        .lines()
-        .map(|line| line.parse::<i64>().unwrap())
-        .into_iter()
-        .enumerate()
-        .collect();
-    numbers
+use crate::error::{parse_lines, AocError};
+
+fn preprocess(input: &str) -> Result<Vec<(usize, i64)>, AocError> {
+    let numbers = parse_lines(input, |line| {
+        line.parse::<i64>()
+            .map_err(|_| AocError::new(format!("invalid number '{}'", line)))
+    })?;
+    if numbers.len() < 2 {
+        return Err(AocError::new("fewer than 2 numbers"));
+    }
+    if !numbers.contains(&0) {
+        return Err(AocError::new("no number 0"));
+    }
+    Ok(numbers.into_iter().enumerate().collect())
 }
 
 fn decrypt(numbers: Vec<(usize, i64)>, key: i64, rounds: usize) -> i64 {
@@ -41,18 +47,18 @@ fn decrypt(numbers: Vec<(usize, i64)>, key: i64, rounds: usize) -> i64 {
     n1000.1 + n2000.1 + n3000.1
 }
 
-fn part1(input: &str) -> String {
-    let numbers = preprocess(input);
-    decrypt(numbers, 1, 1).to_string()
+fn part1(input: &str) -> Result<String, AocError> {
+    let numbers = preprocess(input)?;
+    Ok(decrypt(numbers, 1, 1).to_string())
 }
 
-fn part2(input: &str) -> String {
-    let numbers = preprocess(input);
-    decrypt(numbers, 811589153, 10).to_string()
+fn part2(input: &str) -> Result<String, AocError> {
+    let numbers = preprocess(input)?;
+    Ok(decrypt(numbers, 811589153, 10).to_string())
 }
 
-crate::run!();
+crate::run!("Grove Positioning System", preprocess);
 
 crate::test_example_aoc!(3, 1623178306);
 
-crate::test_aoc!(15297, 2897373276210usize);
+crate::test_aoc!();
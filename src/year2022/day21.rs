@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::error::{parse_lines, AocError};
+
 #[derive(Debug, Clone)]
 enum Job {
     Num(f64),
@@ -9,27 +11,49 @@ enum Job {
     Mul(String, String),
 }
 
-fn preprocess(input: &str) -> HashMap<String, Job> {
-    input
-        .lines()
-        .map(|line| {
-            let name = line[..4].to_owned();
-            let job = if line.chars().nth(6).unwrap().is_ascii_digit() {
-                Job::Num(line[6..].parse::<f64>().unwrap())
-            } else {
-                let op1 = line[6..10].to_owned();
-                let op2 = line[13..17].to_owned();
-                match line.chars().nth(11).unwrap() {
-                    '+' => Job::Add(op1, op2),
-                    '-' => Job::Sub(op1, op2),
-                    '*' => Job::Mul(op1, op2),
-                    '/' => Job::Div(op1, op2),
-                    _ => panic!("Invalid operation"),
+impl Job {
+    /// The monkeys whose numbers the operation waits for.
+    fn operands(&self) -> Option<(&str, &str)> {
+        match self {
+            Job::Num(_) => None,
+            Job::Add(op1, op2) | Job::Sub(op1, op2) | Job::Div(op1, op2) | Job::Mul(op1, op2) => Some((op1, op2)),
+        }
+    }
+}
+
+fn preprocess(input: &str) -> Result<HashMap<String, Job>, AocError> {
+    let jobs = parse_lines(input, |line| {
+        let invalid = || AocError::new(format!("invalid job '{}'", line));
+        let (name, job) = line.split_once(": ").ok_or_else(invalid)?;
+        let job = match job.split(' ').collect::<Vec<_>>()[..] {
+            [number] => Job::Num(number.parse::<f64>().map_err(|_| invalid())?),
+            [op1, operation, op2] => {
+                let (op1, op2) = (op1.to_owned(), op2.to_owned());
+                match operation {
+                    "+" => Job::Add(op1, op2),
+                    "-" => Job::Sub(op1, op2),
+                    "*" => Job::Mul(op1, op2),
+                    "/" => Job::Div(op1, op2),
+                    _ => return Err(invalid()),
                 }
-            };
-            (name, job)
-        })
-        .collect()
+            }
+            _ => return Err(invalid()),
+        };
+        Ok((name.to_owned(), job))
+    })?;
+    let monkeys = jobs.into_iter().collect::<HashMap<_, _>>();
+
+    if !monkeys.contains_key("root") {
+        return Err(AocError::new("no root monkey"));
+    }
+    for job in monkeys.values() {
+        if let Some((op1, op2)) = job.operands() {
+            if let Some(unknown) = [op1, op2].into_iter().find(|op| !monkeys.contains_key(*op)) {
+                return Err(AocError::new(format!("unknown monkey {}", unknown)));
+            }
+        }
+    }
+    Ok(monkeys)
 }
 
 fn get_value(monkeys: &HashMap<String, Job>, monkey: &str) -> f64 {
@@ -42,21 +66,18 @@ fn get_value(monkeys: &HashMap<String, Job>, monkey: &str) -> f64 {
     }
 }
 
-fn part1(input: &str) -> String {
-    let monkeys = preprocess(input);
+fn part1(input: &str) -> Result<String, AocError> {
+    let monkeys = preprocess(input)?;
     let root = get_value(&monkeys, "root");
-    root.to_string()
+    Ok(root.to_string())
 }
 
-fn part2(input: &str) -> String {
-    let mut monkeys = preprocess(input);
-    let (root_left, root_right) = match &monkeys["root"] {
-        Job::Num(_) => unreachable!(),
-        Job::Add(op1, op2) => (op1.clone(), op2.clone()),
-        Job::Sub(op1, op2) => (op1.clone(), op2.clone()),
-        Job::Div(op1, op2) => (op1.clone(), op2.clone()),
-        Job::Mul(op1, op2) => (op1.clone(), op2.clone()),
-    };
+fn part2(input: &str) -> Result<String, AocError> {
+    let mut monkeys = preprocess(input)?;
+    let (root_left, root_right) = monkeys["root"]
+        .operands()
+        .ok_or_else(|| AocError::new("the root monkey does not wait for others"))?;
+    let (root_left, root_right) = (root_left.to_owned(), root_right.to_owned());
 
     // Binary search
     let mut result = 0.0;
@@ -90,11 +111,11 @@ fn part2(input: &str) -> String {
         }
     }
 
-    result.to_string()
+    Ok(result.to_string())
 }
 
-crate::run!();
+crate::run!("Monkey Math", preprocess);
 
 crate::test_example_aoc!(152, 301);
 
-crate::test_aoc!(256997859093114f64, 3952288690726u64);
+crate::test_aoc!();
@@ -1,3 +1,8 @@
+use crate::error::AocError;
+use crate::geometry::Dir4;
+use crate::grid::Grid;
+use crate::solution::PuzzleParams;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Tile {
     Void,
@@ -6,38 +11,78 @@ enum Tile {
 }
 
 impl Tile {
-    fn from_char(c: char) -> Self {
+    fn from_char(c: char) -> Result<Self, AocError> {
         match c {
-            ' ' => Tile::Void,
-            '.' => Tile::Open,
-            '#' => Tile::Wall,
-            _ => Tile::Void,
+            ' ' => Ok(Tile::Void),
+            '.' => Ok(Tile::Open),
+            '#' => Ok(Tile::Wall),
+            _ => Err(AocError::new(format!("invalid tile {:?}", c))),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Direction {
-    Right = 0,
-    Down = 1,
-    Left = 2,
-    Up = 3,
-}
-
 #[derive(Debug, Clone, Copy)]
 enum Instruction {
     Move(i32),
-    Turn(Direction),
+    Turn(Turn),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Turn {
+    Left,
+    Right,
+}
+
+/// How the faces of the cube are laid out on the map: the example and the
+/// puzzle inputs fold differently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    Example,
+    Input,
+}
+
+impl std::fmt::Display for Layout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Layout::Example => "example",
+            Layout::Input => "input",
+        })
+    }
+}
+
+impl std::str::FromStr for Layout {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "example" => Ok(Layout::Example),
+            "input" => Ok(Layout::Input),
+            _ => Err(()),
+        }
+    }
 }
 
 struct Map {
-    map: Vec<Vec<Tile>>,
+    map: Grid<Tile>,
     cube_size: i32,
+    layout: Layout,
+    /// The leftmost open tile of the top row.
+    start: Position,
 }
 
 impl Map {
-    fn new(map: Vec<Vec<Tile>>, cube_size: i32) -> Self {
-        Self { map, cube_size }
+    fn new(map: Grid<Tile>, cube_size: i32, layout: Layout) -> Result<Self, AocError> {
+        let start = map
+            .rows()
+            .next()
+            .and_then(|row| row.iter().position(|&tile| tile == Tile::Open))
+            .ok_or_else(|| AocError::new("no open tile on the top row"))?;
+        Ok(Self { map, cube_size, layout, start: (0, start as i32) })
+    }
+
+    /// The tile at a position, void out of the map.
+    fn tile(&self, pos: Position) -> Tile {
+        self.map.get((pos.0 as isize, pos.1 as isize)).copied().unwrap_or(Tile::Void)
     }
 }
 
@@ -48,263 +93,284 @@ impl Map {
     fn move_position(
         &self,
         mut pos: Position,
-        mut dir: Direction,
+        mut dir: Dir4,
         count: i32,
         wrap_cube: bool,
-    ) -> (Position, Direction) {
+    ) -> Result<(Position, Dir4), AocError> {
         for _ in 0..count as usize {
             let (mut next, mut next_dir) = if wrap_cube {
-                self.move_wrap_cube(pos, dir)
+                self.move_wrap_cube(pos, dir)?
             } else {
                 self.move_wrap(pos, dir)
             };
-            // println!("  {:?} {:?} {:?}", next, next_dir, self.map[next.0 as usize][next.1 as usize]);
-            while self.map[next.0 as usize][next.1 as usize] == Tile::Void {
+            while self.tile(next) == Tile::Void {
                 (next, next_dir) = if wrap_cube {
-                    self.move_wrap_cube(next, next_dir)
+                    self.move_wrap_cube(next, next_dir)?
                 } else {
                     self.move_wrap(next, next_dir)
                 };
             }
 
-            if self.map[next.0 as usize][next.1 as usize] == Tile::Wall {
-                return (pos, dir);
+            if self.tile(next) == Tile::Wall {
+                return Ok((pos, dir));
             }
 
             pos = next;
             dir = next_dir;
         }
 
-        (pos, dir)
+        Ok((pos, dir))
     }
 
-    fn move_wrap(&self, mut pos: Position, dir: Direction) -> (Position, Direction) {
-        // println!("{:?} {:?}", pos, dir);
-        match dir {
-            Direction::Right => pos.1 += 1,
-            Direction::Down => pos.0 += 1,
-            Direction::Left => pos.1 -= 1,
-            Direction::Up => pos.0 -= 1,
-        }
-
-        if pos.0 == -1 {
-            pos.0 = self.map.len() as i32 - 1;
-        }
-
-        if pos.0 == self.map.len() as i32 {
-            pos.0 = 0;
-        }
-
-        if pos.1 == -1 {
-            pos.1 = self.map[0].len() as i32 - 1;
-        }
-
-        if pos.1 == self.map[0].len() as i32 {
-            pos.1 = 0;
-        }
+    fn move_wrap(&self, mut pos: Position, dir: Dir4) -> (Position, Dir4) {
+        let step = dir.offset::<i32>();
+        pos = (pos.0 + step.y, pos.1 + step.x);
 
-        // println!("  > {:?} {:?}", pos, dir);
+        let (row, col) = self.map.wrap((pos.0 as isize, pos.1 as isize));
 
-        (pos, dir)
+        ((row as i32, col as i32), dir)
     }
 
-    fn move_wrap_cube(&self, mut pos: Position, mut dir: Direction) -> (Position, Direction) {
+    fn move_wrap_cube(&self, mut pos: Position, mut dir: Dir4) -> Result<(Position, Dir4), AocError> {
         let face = (pos.0 / self.cube_size, pos.1 / self.cube_size);
         let relative_pos = (pos.0 % self.cube_size, pos.1 % self.cube_size);
 
-        match dir {
-            Direction::Right => pos.1 += 1,
-            Direction::Down => pos.0 += 1,
-            Direction::Left => pos.1 -= 1,
-            Direction::Up => pos.0 -= 1,
-        }
-        // pos = (pos.0.rem_euclid(4 * self.cube_size), pos.1.rem_euclid(4 * self.cube_size));
-
-        if pos.0 < 0
-            || pos.0 >= self.map.len() as i32
-            || pos.1 < 0
-            || pos.1 >= self.map.len() as i32
-            || self.map[pos.0 as usize][pos.1 as usize] == Tile::Void
-        {
+        let step = dir.offset::<i32>();
+        pos = (pos.0 + step.y, pos.1 + step.x);
+
+        if self.tile(pos) == Tile::Void {
             let (next_face, next_dir, next_relative_pos) =
-                self.wrap_position_cube(face, dir, relative_pos);
+                self.wrap_position_cube(face, dir, relative_pos)?;
             pos = (
                 next_face.0 * self.cube_size + next_relative_pos.0,
                 next_face.1 * self.cube_size + next_relative_pos.1,
             );
             dir = next_dir;
+            if self.tile(pos) == Tile::Void {
+                return Err(self.unsupported_layout());
+            }
         }
-        // println!("  > {:?} {:?}", pos, dir);
 
-        (pos, dir)
+        Ok((pos, dir))
+    }
+
+    /// The error when the map does not fold into a cube with the layout and
+    /// the size of faces given.
+    fn unsupported_layout(&self) -> AocError {
+        AocError::new(format!(
+            "the map does not fold as the {} layout with faces of size {}",
+            self.layout, self.cube_size
+        ))
     }
 
     fn wrap_position_cube(
         &self,
         face: FaceIndex,
-        dir: Direction,
+        dir: Dir4,
         pos: Position,
-    ) -> (FaceIndex, Direction, Position) {
+    ) -> Result<(FaceIndex, Dir4, Position), AocError> {
         let c = self.cube_size - 1;
-        match (face, dir) {
-            ((0, 1), Direction::Left) => ((2, 0), Direction::Right, (c - pos.0, 0)),
-            ((0, 1), Direction::Up) => ((3, 0), Direction::Right, (pos.1, 0)),
-            ((0, 2), Direction::Down) => ((1, 1), Direction::Left, (pos.1, c)),
-            ((0, 2), Direction::Right) => ((2, 1), Direction::Left, (c - pos.0, c)),
-            ((0, 2), Direction::Up) => ((3, 0), Direction::Up, (c, pos.1)),
-            ((1, 1), Direction::Left) => ((2, 0), Direction::Down, (0, pos.0)),
-            ((1, 1), Direction::Right) => ((0, 2), Direction::Up, (c, pos.0)),
-            ((2, 0), Direction::Left) => ((0, 1), Direction::Right, (c - pos.0, 0)),
-            ((2, 0), Direction::Up) => ((1, 1), Direction::Right, (pos.1, 0)),
-            ((2, 1), Direction::Down) => ((3, 0), Direction::Left, (pos.1, c)),
-            ((2, 1), Direction::Right) => ((0, 2), Direction::Left, (c - pos.0, c)),
-            ((3, 0), Direction::Down) => ((0, 2), Direction::Down, (0, pos.1)),
-            ((3, 0), Direction::Left) => ((0, 1), Direction::Down, (0, pos.0)),
-            ((3, 0), Direction::Right) => ((2, 1), Direction::Up, (c, pos.0)),
-            // _ => (face, dir, pos),
-            _ => panic!(),
-        }
+        let wrapped = match self.layout {
+            Layout::Example => Self::wrap_example(face, dir, pos, c),
+            Layout::Input => Self::wrap_input(face, dir, pos, c),
+        };
+        wrapped.ok_or_else(|| self.unsupported_layout())
+    }
+
+    fn wrap_example(face: FaceIndex, dir: Dir4, pos: Position, c: i32) -> Option<(FaceIndex, Dir4, Position)> {
+        let wrapped = match (face, dir) {
+            ((0, 2), Dir4::Right) => ((2, 3), Dir4::Left, (c - pos.0, c)),
+            ((0, 2), Dir4::Left) => ((1, 1), Dir4::Down, (0, pos.0)),
+            ((0, 2), Dir4::Up) => ((1, 0), Dir4::Down, (0, c - pos.1)),
+            ((1, 0), Dir4::Down) => ((2, 2), Dir4::Up, (c, c - pos.1)),
+            ((1, 0), Dir4::Left) => ((2, 3), Dir4::Up, (c, c - pos.0)),
+            ((1, 0), Dir4::Up) => ((0, 2), Dir4::Down, (0, c - pos.1)),
+            ((1, 1), Dir4::Down) => ((2, 2), Dir4::Right, (c - pos.1, 0)),
+            ((1, 1), Dir4::Up) => ((0, 2), Dir4::Right, (pos.1, 0)),
+            ((1, 2), Dir4::Right) => ((2, 3), Dir4::Down, (0, c - pos.0)),
+            ((2, 2), Dir4::Down) => ((1, 0), Dir4::Up, (c, c - pos.1)),
+            ((2, 2), Dir4::Left) => ((1, 1), Dir4::Up, (c, c - pos.0)),
+            ((2, 3), Dir4::Right) => ((0, 2), Dir4::Left, (c - pos.0, c)),
+            ((2, 3), Dir4::Down) => ((1, 0), Dir4::Right, (c - pos.1, 0)),
+            ((2, 3), Dir4::Up) => ((1, 2), Dir4::Left, (c - pos.1, c)),
+            _ => return None,
+        };
+        Some(wrapped)
+    }
+
+    fn wrap_input(face: FaceIndex, dir: Dir4, pos: Position, c: i32) -> Option<(FaceIndex, Dir4, Position)> {
+        let wrapped = match (face, dir) {
+            ((0, 1), Dir4::Left) => ((2, 0), Dir4::Right, (c - pos.0, 0)),
+            ((0, 1), Dir4::Up) => ((3, 0), Dir4::Right, (pos.1, 0)),
+            ((0, 2), Dir4::Down) => ((1, 1), Dir4::Left, (pos.1, c)),
+            ((0, 2), Dir4::Right) => ((2, 1), Dir4::Left, (c - pos.0, c)),
+            ((0, 2), Dir4::Up) => ((3, 0), Dir4::Up, (c, pos.1)),
+            ((1, 1), Dir4::Left) => ((2, 0), Dir4::Down, (0, pos.0)),
+            ((1, 1), Dir4::Right) => ((0, 2), Dir4::Up, (c, pos.0)),
+            ((2, 0), Dir4::Left) => ((0, 1), Dir4::Right, (c - pos.0, 0)),
+            ((2, 0), Dir4::Up) => ((1, 1), Dir4::Right, (pos.1, 0)),
+            ((2, 1), Dir4::Down) => ((3, 0), Dir4::Left, (pos.1, c)),
+            ((2, 1), Dir4::Right) => ((0, 2), Dir4::Left, (c - pos.0, c)),
+            ((3, 0), Dir4::Down) => ((0, 2), Dir4::Down, (0, pos.1)),
+            ((3, 0), Dir4::Left) => ((0, 1), Dir4::Down, (0, pos.0)),
+            ((3, 0), Dir4::Right) => ((2, 1), Dir4::Up, (c, pos.0)),
+            _ => return None,
+        };
+        Some(wrapped)
     }
 }
 
-fn apply_instructions(map: &Map, instructions: &[Instruction], wrap_cube: bool) -> i32 {
-    let mut dir = Direction::Right;
-    let mut pos = (0, 50);
+fn apply_instructions(map: &Map, instructions: &[Instruction], wrap_cube: bool) -> Result<i32, AocError> {
+    let mut dir = Dir4::Right;
+    let mut pos = map.start;
     for instruction in instructions {
         match instruction {
             Instruction::Move(count) => {
-                (pos, dir) = map.move_position(pos, dir, *count, wrap_cube);
+                (pos, dir) = map.move_position(pos, dir, *count, wrap_cube)?;
             }
             Instruction::Turn(turn) => {
                 dir = match turn {
-                    Direction::Right => match dir {
-                        Direction::Right => Direction::Down,
-                        Direction::Down => Direction::Left,
-                        Direction::Left => Direction::Up,
-                        Direction::Up => Direction::Right,
-                    },
-                    Direction::Left => match dir {
-                        Direction::Right => Direction::Up,
-                        Direction::Down => Direction::Right,
-                        Direction::Left => Direction::Down,
-                        Direction::Up => Direction::Left,
-                    },
-                    _ => unreachable!(),
+                    Turn::Right => dir.turn_right(),
+                    Turn::Left => dir.turn_left(),
                 }
             }
         }
-
-        // println!("{:?} {:?} {:?}", instruction, pos, dir);
     }
 
-    1000 * (pos.0 + 1) + 4 * (pos.1 + 1) + dir as i32
+    Ok(1000 * (pos.0 + 1) + 4 * (pos.1 + 1) + dir as i32)
 }
 
-fn preprocess(input: &str) -> (Map, Vec<Instruction>) {
-    let size = 50;
-    let map = input
-        .lines()
-        .take(4 * size)
-        .map(|line| {
-            let mut v = line.chars().map(Tile::from_char).collect::<Vec<_>>();
-            v.resize(4 * size, Tile::Void);
-            v
-        })
-        .collect::<Vec<_>>();
-
-    let map = Map::new(map, size as i32);
-
-    let line = input.lines().last().unwrap();
+fn preprocess(input: &str, params: &PuzzleParams) -> Result<(Map, Vec<Instruction>), AocError> {
+    let size = params.get("size", 4, 50)?;
+    if size <= 0 {
+        return Err(AocError::new(format!("invalid size of faces {}", size)));
+    }
+    let layout = params.get("layout", Layout::Example, Layout::Input)?;
+    let (map, path) = input
+        .split_once("\n\n")
+        .ok_or_else(|| AocError::new("no path after the map"))?;
+    let map = Grid::try_parse(map, Tile::from_char)?;
+
+    let map = Map::new(map, size, layout)?;
+
+    let line = path.trim_end();
+    let parse_move = |count: &str| {
+        count
+            .parse::<i32>()
+            .map(Instruction::Move)
+            .map_err(|_| AocError::new(format!("invalid move '{}'", count)).at_line(input.lines().count()))
+    };
     let mut instructions = Vec::new();
     let mut start = 0;
     while let Some(end) = line[start..].find(['R', 'L']) {
-        let direction = line[start..].chars().nth(end).unwrap();
-        instructions.push(Instruction::Move(
-            line[start..start + end].parse::<i32>().unwrap(),
-        ));
-        instructions.push(Instruction::Turn(if direction == 'R' {
-            Direction::Right
+        instructions.push(parse_move(&line[start..start + end])?);
+        instructions.push(Instruction::Turn(if line[start + end..].starts_with('R') {
+            Turn::Right
         } else {
-            Direction::Left
+            Turn::Left
         }));
         start += end + 1;
     }
-    instructions.push(Instruction::Move(line[start..].parse::<i32>().unwrap()));
+    instructions.push(parse_move(&line[start..])?);
 
-    (map, instructions)
+    Ok((map, instructions))
 }
 
-fn part1(input: &str) -> String {
-    let (map, instructions) = preprocess(input);
-    let result = apply_instructions(&map, &instructions, false);
-    result.to_string()
+fn part1(input: &str, params: &PuzzleParams) -> Result<String, AocError> {
+    let (map, instructions) = preprocess(input, params)?;
+    let result = apply_instructions(&map, &instructions, false)?;
+    Ok(result.to_string())
 }
 
-fn part2(input: &str) -> String {
-    let (map, instructions) = preprocess(input);
-
-    // Tests
-    assert_eq!(
-        map.move_wrap_cube((0, 50), Direction::Up),
-        ((150, 0), Direction::Right)
-    );
-    assert_eq!(
-        map.move_wrap_cube((0, 50), Direction::Left),
-        ((149, 0), Direction::Right)
-    );
-    assert_eq!(
-        map.move_wrap_cube((49, 149), Direction::Down),
-        ((99, 99), Direction::Left)
-    );
-    assert_eq!(
-        map.move_wrap_cube((49, 149), Direction::Right),
-        ((100, 99), Direction::Left)
-    );
-    assert_eq!(
-        map.move_wrap_cube((0, 100), Direction::Up),
-        ((199, 0), Direction::Up)
-    );
-    assert_eq!(
-        map.move_wrap_cube((50, 50), Direction::Left),
-        ((100, 0), Direction::Down)
-    );
-    assert_eq!(
-        map.move_wrap_cube((50, 99), Direction::Right),
-        ((49, 100), Direction::Up)
-    );
-    assert_eq!(
-        map.move_wrap_cube((100, 0), Direction::Left),
-        ((49, 50), Direction::Right)
-    );
-    assert_eq!(
-        map.move_wrap_cube((100, 0), Direction::Up),
-        ((50, 50), Direction::Right)
-    );
-    assert_eq!(
-        map.move_wrap_cube((149, 99), Direction::Down),
-        ((199, 49), Direction::Left)
-    );
-    assert_eq!(
-        map.move_wrap_cube((149, 99), Direction::Right),
-        ((0, 149), Direction::Left)
-    );
-    assert_eq!(
-        map.move_wrap_cube((199, 0), Direction::Down),
-        ((0, 100), Direction::Down)
-    );
-    assert_eq!(
-        map.move_wrap_cube((199, 0), Direction::Left),
-        ((0, 99), Direction::Down)
-    );
-    assert_eq!(
-        map.move_wrap_cube((199, 49), Direction::Right),
-        ((149, 99), Direction::Up)
-    );
-
-    let result = apply_instructions(&map, &instructions, true);
-    result.to_string()
+fn part2(input: &str, params: &PuzzleParams) -> Result<String, AocError> {
+    let (map, instructions) = preprocess(input, params)?;
+    let result = apply_instructions(&map, &instructions, true)?;
+    Ok(result.to_string())
 }
 
-crate::run!();
+crate::run!("Monkey Map", preprocess);
+
+crate::test_example_aoc!(6032, 5031);
+
+crate::test_aoc!();
+
+#[cfg(test)]
+mod wrap {
+    use super::*;
+
+    #[test]
+    fn input_layout() {
+        let solution = crate::solution::find(2022, 22).unwrap();
+        let Some((input, _)) = crate::input::load_or_skip(solution, &crate::input::Source::Puzzle) else { return };
+        let (map, _) = preprocess(&input, &PuzzleParams::default()).unwrap();
+
+        assert_eq!(
+            map.move_wrap_cube((0, 50), Dir4::Up).unwrap(),
+            ((150, 0), Dir4::Right)
+        );
+        assert_eq!(
+            map.move_wrap_cube((0, 50), Dir4::Left).unwrap(),
+            ((149, 0), Dir4::Right)
+        );
+        assert_eq!(
+            map.move_wrap_cube((49, 149), Dir4::Down).unwrap(),
+            ((99, 99), Dir4::Left)
+        );
+        assert_eq!(
+            map.move_wrap_cube((49, 149), Dir4::Right).unwrap(),
+            ((100, 99), Dir4::Left)
+        );
+        assert_eq!(
+            map.move_wrap_cube((0, 100), Dir4::Up).unwrap(),
+            ((199, 0), Dir4::Up)
+        );
+        assert_eq!(
+            map.move_wrap_cube((50, 50), Dir4::Left).unwrap(),
+            ((100, 0), Dir4::Down)
+        );
+        assert_eq!(
+            map.move_wrap_cube((50, 99), Dir4::Right).unwrap(),
+            ((49, 100), Dir4::Up)
+        );
+        assert_eq!(
+            map.move_wrap_cube((100, 0), Dir4::Left).unwrap(),
+            ((49, 50), Dir4::Right)
+        );
+        assert_eq!(
+            map.move_wrap_cube((100, 0), Dir4::Up).unwrap(),
+            ((50, 50), Dir4::Right)
+        );
+        assert_eq!(
+            map.move_wrap_cube((149, 99), Dir4::Down).unwrap(),
+            ((199, 49), Dir4::Left)
+        );
+        assert_eq!(
+            map.move_wrap_cube((149, 99), Dir4::Right).unwrap(),
+            ((0, 149), Dir4::Left)
+        );
+        assert_eq!(
+            map.move_wrap_cube((199, 0), Dir4::Down).unwrap(),
+            ((0, 100), Dir4::Down)
+        );
+        assert_eq!(
+            map.move_wrap_cube((199, 0), Dir4::Left).unwrap(),
+            ((0, 99), Dir4::Down)
+        );
+        assert_eq!(
+            map.move_wrap_cube((199, 49), Dir4::Right).unwrap(),
+            ((149, 99), Dir4::Up)
+        );
+    }
 
-crate::test_aoc!(146092, 110342);
+    #[test]
+    fn unsupported_layout() {
+        let solution = crate::solution::find(2022, 22).unwrap();
+        let Some((input, _)) = crate::input::load_or_skip(solution, &crate::input::Source::Example) else { return };
+        let mut params = PuzzleParams::example();
+        params.overrides.insert("layout".to_owned(), "input".to_owned());
+        let error = part2(&input, &params).unwrap_err();
+        assert_eq!(error.cause, "the map does not fold as the input layout with faces of size 4");
+        params.overrides.insert("size".to_owned(), "0".to_owned());
+        assert!(part1(&input, &params).is_err());
+    }
+}
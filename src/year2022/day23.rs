@@ -1,122 +1,73 @@
 use std::collections::{HashMap, HashSet};
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    North,
-    NorthWest,
-    West,
-    SouthWest,
-    South,
-    SouthEast,
-    East,
-    NorthEast,
-}
+use crate::geometry::{Dir8, Point2};
+use crate::grid::{Cells, SparseGrid};
+
+type Position = Point2<isize>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Elf;
 
-type Position = (isize, isize);
-
-fn preprocess(input: &str) -> HashSet<Position> {
-    input
-        .lines()
-        .enumerate()
-        .flat_map(|(i, line)| {
-            line.chars()
-                .enumerate()
-                .map(move |(j, ch)| (i as isize, j as isize, ch))
-        })
-        .filter_map(|(i, j, ch)| if ch == '#' { Some((i, j)) } else { None })
-        .collect::<HashSet<_>>()
+type Elves = SparseGrid<Elf>;
+
+fn preprocess(input: &str) -> Elves {
+    SparseGrid::parse(input, |ch| (ch == '#').then_some(Elf))
 }
 
-fn neighbor(pos: Position, dir: Direction) -> Position {
-    match dir {
-        Direction::North => (pos.0 - 1, pos.1),
-        Direction::NorthWest => (pos.0 - 1, pos.1 - 1),
-        Direction::West => (pos.0, pos.1 - 1),
-        Direction::SouthWest => (pos.0 + 1, pos.1 - 1),
-        Direction::South => (pos.0 + 1, pos.1),
-        Direction::SouthEast => (pos.0 + 1, pos.1 + 1),
-        Direction::East => (pos.0, pos.1 + 1),
-        Direction::NorthEast => (pos.0 - 1, pos.1 + 1),
-    }
+/// The cell of the grid of elves at a position.
+fn cell(pos: Position) -> (isize, isize) {
+    (pos.y, pos.x)
 }
 
-fn has_neighbor(elves: &HashSet<Position>, elf: Position) -> bool {
-    elves.contains(&neighbor(elf, Direction::North))
-        || elves.contains(&neighbor(elf, Direction::NorthWest))
-        || elves.contains(&neighbor(elf, Direction::West))
-        || elves.contains(&neighbor(elf, Direction::SouthWest))
-        || elves.contains(&neighbor(elf, Direction::South))
-        || elves.contains(&neighbor(elf, Direction::SouthEast))
-        || elves.contains(&neighbor(elf, Direction::East))
-        || elves.contains(&neighbor(elf, Direction::NorthEast))
+fn has_neighbor(elves: &Elves, elf: Position) -> bool {
+    elf.neighbours8().any(|pos| elves.contains(cell(pos)))
 }
 
-fn can_move(elves: &HashSet<Position>, elf: Position, dir: Direction) -> bool {
-    match dir {
-        Direction::North => {
-            !elves.contains(&neighbor(elf, Direction::North))
-                && !elves.contains(&neighbor(elf, Direction::NorthWest))
-                && !elves.contains(&neighbor(elf, Direction::NorthEast))
-        }
-        Direction::West => {
-            !elves.contains(&neighbor(elf, Direction::West))
-                && !elves.contains(&neighbor(elf, Direction::SouthWest))
-                && !elves.contains(&neighbor(elf, Direction::NorthWest))
-        }
-        Direction::South => {
-            !elves.contains(&neighbor(elf, Direction::South))
-                && !elves.contains(&neighbor(elf, Direction::SouthWest))
-                && !elves.contains(&neighbor(elf, Direction::SouthEast))
-        }
-        Direction::East => {
-            !elves.contains(&neighbor(elf, Direction::East))
-                && !elves.contains(&neighbor(elf, Direction::SouthEast))
-                && !elves.contains(&neighbor(elf, Direction::NorthEast))
-        }
-        _ => unreachable!(),
-    }
+/// Whether no elf is in the direction, nor diagonally on either side of it.
+fn can_move(elves: &Elves, elf: Position, dir: Dir8) -> bool {
+    [dir.turn_left(), dir, dir.turn_right()]
+        .into_iter()
+        .all(|dir| !elves.contains(cell(elf + dir.offset())))
 }
 
-fn simulate_round(elves: &HashSet<Position>, round: usize) -> HashSet<Position> {
-    const DIRECTIONS: [Direction; 4] = [
-        Direction::North,
-        Direction::South,
-        Direction::West,
-        Direction::East,
-    ];
+fn simulate_round(elves: &Elves, round: usize) -> Elves {
+    const DIRECTIONS: [Dir8; 4] = [Dir8::N, Dir8::S, Dir8::W, Dir8::E];
 
     let mut proposals: HashMap<Position, HashSet<Position>> = HashMap::new();
 
-    for elf in elves {
-        if !has_neighbor(elves, *elf) {
-            proposals.entry(*elf).or_default().insert(*elf);
+    for ((row, col), _) in elves.iter() {
+        let elf = Position::new(col, row);
+        if !has_neighbor(elves, elf) {
+            proposals.entry(elf).or_default().insert(elf);
             continue;
         }
 
         let mut could_move = false;
         for k in 0..DIRECTIONS.len() {
             let dir = DIRECTIONS[(round + k) % DIRECTIONS.len()];
-            if can_move(elves, *elf, dir) {
+            if can_move(elves, elf, dir) {
                 could_move = true;
                 proposals
-                    .entry(neighbor(*elf, dir))
+                    .entry(elf + dir.offset())
                     .or_default()
-                    .insert(*elf);
+                    .insert(elf);
                 break;
             }
         }
 
         if !could_move {
-            proposals.entry(*elf).or_default().insert(*elf);
+            proposals.entry(elf).or_default().insert(elf);
         }
     }
 
-    let mut new_elves = HashSet::new();
+    let mut new_elves = SparseGrid::default();
     for (next, original) in &proposals {
         if original.len() == 1 {
-            new_elves.insert(*next);
+            new_elves.set(cell(*next), Elf);
         } else {
-            new_elves.extend(original);
+            for &elf in original {
+                new_elves.set(cell(elf), Elf);
+            }
         }
     }
 
@@ -130,11 +81,7 @@ fn part1(input: &str) -> String {
         elves = simulate_round(&elves, round);
     }
 
-    let min_i = elves.iter().map(|e| e.0).min().unwrap();
-    let max_i = elves.iter().map(|e| e.0).max().unwrap();
-    let min_j = elves.iter().map(|e| e.1).min().unwrap();
-    let max_j = elves.iter().map(|e| e.1).max().unwrap();
-    let empty = (max_i - min_i + 1) * (max_j - min_j + 1) - elves.len() as isize;
+    let empty = elves.bounds().area() - elves.len();
     empty.to_string()
 }
 
@@ -155,8 +102,8 @@ fn part2(input: &str) -> String {
     round.to_string()
 }
 
-crate::run!();
+crate::run!("Unstable Diffusion", preprocess);
 
 crate::test_example_aoc!(110, 20);
 
-crate::test_aoc!(4068, 968);
+crate::test_aoc!();
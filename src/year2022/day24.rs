@@ -1,28 +1,21 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashSet},
-};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
+use crate::error::AocError;
+use crate::geometry::Dir4;
+use crate::search;
 
 type Position = (isize, isize);
 
 #[derive(Debug, Clone, Copy)]
 struct Blizzard {
     position: Position,
-    direction: Direction,
+    direction: Dir4,
 }
 
 #[derive(Debug)]
 struct Map {
     width: isize,
     height: isize,
+    /// The blizzards for each minute of their period: they are back where
+    /// they started after `lcm(width, height)` minutes.
     maps: Vec<Vec<Vec<bool>>>,
 }
 
@@ -37,7 +30,7 @@ impl Map {
             return false;
         }
 
-        !self.maps[time as usize][position.0 as usize][position.1 as usize]
+        !self.maps[time as usize % self.maps.len()][position.0 as usize][position.1 as usize]
     }
 
     fn get_target(&self) -> Position {
@@ -49,50 +42,49 @@ impl Map {
     }
 }
 
-fn preprocess(input: &str) -> Map {
-    let width = input.lines().next().unwrap().len() - 2;
-    let height = input.lines().count() - 2;
+fn preprocess(input: &str) -> Result<Map, AocError> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let (Some(width), Some(height)) = (lines.first().and_then(|line| line.len().checked_sub(2)), lines.len().checked_sub(2))
+    else {
+        return Err(AocError::new("no valley"));
+    };
+    if width == 0 || height == 0 {
+        return Err(AocError::new("no valley"));
+    }
 
     let mut blizzards = Vec::new();
-    for (row, line) in input.lines().enumerate() {
+    for (row, line) in lines.iter().enumerate() {
+        if line.len() != width + 2 {
+            return Err(AocError::new(format!("invalid row '{}'", line)).at_line(row + 1));
+        }
         for (col, ch) in line.char_indices() {
             let blizzard = match ch {
-                '>' => Some(Blizzard {
-                    position: (row as isize - 1, col as isize - 1),
-                    direction: Direction::Right,
-                }),
-                '<' => Some(Blizzard {
-                    position: (row as isize - 1, col as isize - 1),
-                    direction: Direction::Left,
-                }),
-                'v' => Some(Blizzard {
-                    position: (row as isize - 1, col as isize - 1),
-                    direction: Direction::Down,
-                }),
-                '^' => Some(Blizzard {
+                '#' | '.' => None,
+                _ => Some(Blizzard {
                     position: (row as isize - 1, col as isize - 1),
-                    direction: Direction::Up,
+                    direction: Dir4::try_from(ch).map_err(|error| AocError::new(error).at_line(row + 1))?,
                 }),
-                _ => None,
             };
 
             if let Some(blizzard) = blizzard {
+                if row == 0 || row > height || col == 0 || col > width {
+                    return Err(AocError::new("blizzard in a wall").at_line(row + 1));
+                }
                 blizzards.push(blizzard);
             }
         }
     }
 
-    let mut maps = Vec::new();
-    for time in 0..800 {
-        let map = compute_map(&blizzards, time, width, height);
-        maps.push(map);
-    }
+    let period = width / gcd(width, height) * height;
+    let maps = (0..period)
+        .map(|time| compute_map(&blizzards, time as i32, width, height))
+        .collect();
 
-    Map {
+    Ok(Map {
         width: width as isize,
         height: height as isize,
         maps,
-    }
+    })
 }
 
 fn compute_map(
@@ -101,111 +93,90 @@ fn compute_map(
     width: usize,
     height: usize,
 ) -> Vec<Vec<bool>> {
-    let mut map = vec![vec![false; width as usize]; height as usize];
+    let mut map = vec![vec![false; width]; height];
     for blizzard in blizzards {
-        let pos = match blizzard.direction {
-            Direction::Up => (
-                (blizzard.position.0 - time as isize).rem_euclid(height as isize),
-                blizzard.position.1,
-            ),
-            Direction::Right => (
-                blizzard.position.0,
-                (blizzard.position.1 + time as isize).rem_euclid(width as isize),
-            ),
-            Direction::Down => (
-                (blizzard.position.0 + time as isize).rem_euclid(height as isize),
-                blizzard.position.1,
-            ),
-            Direction::Left => (
-                blizzard.position.0,
-                (blizzard.position.1 - time as isize).rem_euclid(width as isize),
-            ),
-        };
+        let step = blizzard.direction.offset::<isize>() * time as isize;
+        let pos = (
+            (blizzard.position.0 + step.y).rem_euclid(height as isize),
+            (blizzard.position.1 + step.x).rem_euclid(width as isize),
+        );
         map[pos.0 as usize][pos.1 as usize] = true;
     }
     map
 }
 
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// A position at a minute of the blizzards' period: coming back to it later
+/// in another period gets nowhere new.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct State {
     pos: Position,
-    time: u32,
+    phase: u32,
 }
 
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .time
-            .cmp(&self.time)
-            .then_with(|| self.pos.cmp(&other.pos))
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-fn find_path(map: &Map, start: Position, end: Position, start_time: u32) -> u32 {
-    let mut to_process = BinaryHeap::new();
-    to_process.push(State {
+/// The time when reaching `end` from `start`, leaving at `start_time`.
+fn find_path(map: &Map, start: Position, end: Position, start_time: u32) -> Result<u32, AocError> {
+    let period = map.maps.len() as u32;
+    let successors = |state: &State| {
+        let (row, col) = state.pos;
+        let phase = (state.phase + 1) % period;
+        [(row + 1, col), (row - 1, col), (row, col + 1), (row, col - 1), (row, col)]
+            .into_iter()
+            .filter(move |&pos| map.can_move(pos, phase))
+            .map(move |pos| (State { pos, phase }, 1))
+    };
+    let distance = |state: &State| (state.pos.0.abs_diff(end.0) + state.pos.1.abs_diff(end.1)) as u32;
+    let start = State {
         pos: start,
-        time: start_time,
-    });
-
-    let mut processed = HashSet::new();
-
-    while !to_process.is_empty() {
-        let state = to_process.pop().unwrap();
-
-        if processed.contains(&state) {
-            continue;
-        }
-        processed.insert(state);
+        phase: start_time % period,
+    };
+    search::astar([start], successors, distance, |state| state.pos == end)
+        .map(|cost| start_time + cost)
+        .ok_or_else(|| AocError::new("no path through the blizzards"))
+}
 
-        if state.pos == end {
-            return state.time;
-        }
+fn part1(input: &str) -> Result<String, AocError> {
+    let map = preprocess(input)?;
 
-        let mut try_move = |pos| {
-            if map.can_move(pos, state.time + 1) {
-                to_process.push(State {
-                    pos,
-                    time: state.time + 1,
-                });
-            }
-        };
-        try_move((state.pos.0 + 1, state.pos.1));
-        try_move((state.pos.0 - 1, state.pos.1));
-        try_move((state.pos.0, state.pos.1 + 1));
-        try_move((state.pos.0, state.pos.1 - 1));
-        try_move((state.pos.0, state.pos.1));
-    }
+    let time = find_path(&map, map.get_source(), map.get_target(), 0)?;
 
-    0
+    Ok(time.to_string())
 }
 
-fn part1(input: &str) -> String {
-    let map = preprocess(input);
+fn part2(input: &str) -> Result<String, AocError> {
+    let map = preprocess(input)?;
 
-    let time = find_path(&map, map.get_source(), map.get_target(), 0);
+    let time = find_path(&map, map.get_source(), map.get_target(), 0)?;
+    let time = find_path(&map, map.get_target(), map.get_source(), time)?;
+    let time = find_path(&map, map.get_source(), map.get_target(), time)?;
 
-    time.to_string()
+    Ok(time.to_string())
 }
 
-fn part2(input: &str) -> String {
-    let map = preprocess(input);
+crate::run!("Blizzard Basin", preprocess);
 
-    let time = find_path(&map, map.get_source(), map.get_target(), 0);
-    let time = find_path(&map, map.get_target(), map.get_source(), time);
-    let time = find_path(&map, map.get_source(), map.get_target(), time);
+crate::test_example_aoc!(18, 54);
 
-    time.to_string()
-}
+crate::test_aoc!();
 
-crate::run!();
+#[cfg(test)]
+mod blocked {
+    use super::*;
 
-crate::test_example_aoc!(18, 54);
+    #[test]
+    fn no_path() {
+        let error = part1("#.#\n#v#\n#^#\n#.#\n").unwrap_err();
+        assert_eq!(error.cause, "no path through the blizzards");
 
-crate::test_aoc!(232, 715);
+        // A wider valley whose last column is always swept by blizzards.
+        let mut input = format!("#.{}\n", "#".repeat(30));
+        for row in 0..20 {
+            input += &format!("#{}{}#\n", ".".repeat(29), if row % 2 == 0 { 'v' } else { '^' });
+        }
+        input += &format!("{}.#\n", "#".repeat(30));
+        assert!(part1(&input).is_err());
+    }
+}
@@ -1,4 +1,6 @@
-fn snafu_to_int(snafu: &str) -> i64 {
+use crate::error::{parse_lines, AocError};
+
+fn snafu_to_int(snafu: &str) -> Result<i64, AocError> {
     let mut n = 0;
     for ch in snafu.chars() {
         let d = match ch {
@@ -7,13 +9,13 @@ fn snafu_to_int(snafu: &str) -> i64 {
             '2' => 2,
             '-' => -1,
             '=' => -2,
-            _ => panic!("Invalid digit"),
+            _ => return Err(AocError::new(format!("invalid digit {:?}", ch))),
         };
 
         n = 5 * n + d;
     }
 
-    n
+    Ok(n)
 }
 
 fn int_to_snafu(n: i64) -> String {
@@ -21,16 +23,9 @@ fn int_to_snafu(n: i64) -> String {
 
     let mut snafu = String::new();
     while n > 0 {
-        let d = n % 5;
+        // Digits 3 and 4 are written -2 and -1, carrying 1
+        let (d, c) = [('0', 0), ('1', 0), ('2', 0), ('=', 1), ('-', 1)][(n % 5) as usize];
         n /= 5;
-        let (d, c) = match d {
-            0 => ('0', 0),
-            1 => ('1', 0),
-            2 => ('2', 0),
-            3 => ('=', 1),
-            4 => ('-', 1),
-            _ => panic!("Invalid digit"),
-        };
         n += c;
 
         snafu.push(d);
@@ -39,18 +34,20 @@ fn int_to_snafu(n: i64) -> String {
     snafu.chars().rev().collect()
 }
 
-fn part1(input: &str) -> String {
-    let sum = input.lines().map(snafu_to_int).sum::<i64>();
+fn part1(input: &str) -> Result<String, AocError> {
+    let sum = parse_lines(input, snafu_to_int)?.into_iter().sum::<i64>();
 
-    int_to_snafu(sum)
+    Ok(int_to_snafu(sum))
 }
 
 fn part2(_input: &str) -> String {
     0.to_string()
 }
 
-crate::run!();
+crate::run!("Full of Hot Air");
 
 crate::test_example_aoc!("2=-1=0", 0);
 
-crate::test_aoc!("20=02=120-=-2110-0=1", 0);
+crate::test_examples_aoc!();
+
+crate::test_aoc!();
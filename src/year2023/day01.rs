@@ -1,12 +1,13 @@
-fn part1(input: &str) -> String {
-    let mut sum = 0;
-    for line in input.lines() {
-        let first = line.chars().filter(|c| c.is_numeric()).next().unwrap() as u32 - '0' as u32;
-        let last = line.chars().rev().filter(|c| c.is_numeric()).next().unwrap() as u32 - '0' as u32; 
-        let number = 10 * first + last;
-        sum += number;
-    }
-    sum.to_string()
+use crate::error::{parse_lines, AocError};
+
+fn part1(input: &str) -> Result<String, AocError> {
+    let numbers = parse_lines(input, |line| {
+        let mut digits = line.chars().filter_map(|c| c.to_digit(10));
+        let first = digits.next().ok_or_else(|| AocError::new(format!("no digit in '{}'", line)))?;
+        let last = digits.next_back().unwrap_or(first);
+        Ok(10 * first + last)
+    })?;
+    Ok(numbers.iter().sum::<u32>().to_string())
 }
 
 fn part2(input: &str) -> String {
@@ -17,27 +18,26 @@ fn part2(input: &str) -> String {
         // Find first of names
         let (first_name_index, first_name_value) = names.iter()
             .enumerate()
-            .filter_map(|(name_index, name)| line.find(name).and_then(|index| Some((index, name_index as u32 + 1))))
+            .filter_map(|(name_index, name)| line.find(name).map(|index| (index, name_index as u32 + 1)))
             .min_by_key(|(index, _)| *index)
             .unwrap_or((line.len(), 0));
 
         // Find first digit
         let (first_digit_index, first_digit_value) = line
-            .find(|ch: char| ch.is_numeric())
-            .and_then(|index| Some((index, line.chars().nth(index).unwrap().to_digit(10).unwrap())))
+            .find(|ch: char| ch.is_ascii_digit())
+            .map(|index| (index, (line.as_bytes()[index] - b'0') as u32))
             .unwrap_or((line.len(), 0));
 
         // Find last of names
         let (last_name_index, last_name_value) = names.iter()
             .enumerate()
-            .filter_map(|(name_index, name)| line.rfind(name).and_then(|index| Some((index, name_index as u32 + 1))))
+            .filter_map(|(name_index, name)| line.rfind(name).map(|index| (index, name_index as u32 + 1)))
             .max_by_key(|(index, _)| *index)
             .unwrap_or((0, 0));
 
         // Find last digit
         let (last_digit_index, last_digit_value) = line
-            .rfind(|ch: char| ch.is_numeric())
-            .and_then(|index| Some((index, line.chars().nth(index).unwrap().to_digit(10).unwrap())))
+            .rfind(|ch: char| ch.is_ascii_digit()).map(|index| (index, (line.as_bytes()[index] - b'0') as u32))
             .unwrap_or((0, 0));
 
         // Result
@@ -50,8 +50,8 @@ fn part2(input: &str) -> String {
     sum.to_string()
 }
 
-crate::run!();
+crate::run!("Trebuchet?!");
 
 crate::test_example_aoc!(142, 281);
 
-crate::test_aoc!(55834, 53221);
+crate::test_aoc!();
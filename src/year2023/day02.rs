@@ -1,30 +1,30 @@
-fn parse_input(input: &str) -> Vec<Vec<[u32; 3]>> {
-    let mut games = Vec::new();
-    for line in input.lines() {
+use crate::error::{parse_lines, AocError};
+
+fn parse_input(input: &str) -> Result<Vec<Vec<[u32; 3]>>, AocError> {
+    parse_lines(input, |line| {
         let mut game = Vec::new();
-        let records = line.split(':').nth(1).unwrap();
+        let records = line.split(':').nth(1).ok_or_else(|| AocError::new(format!("invalid game '{}'", line)))?;
         for record in records.split(';') {
             let mut game_cubes = [0, 0, 0];
             for cubes in record.split(", ") {
-                let mut split = cubes.trim().split(' ');
-                let count = split.next().unwrap().parse::<u32>().unwrap();
-                let color = split.next().unwrap();
+                let invalid = || AocError::new(format!("invalid cubes '{}'", cubes.trim()));
+                let (count, color) = cubes.trim().split_once(' ').ok_or_else(invalid)?;
+                let count = count.parse::<u32>().map_err(|_| invalid())?;
                 match color {
                     "red" => game_cubes[0] = count,
                     "green" => game_cubes[1] = count,
                     "blue" => game_cubes[2] = count,
-                    _ => panic!(),
+                    _ => return Err(invalid()),
                 }
             }
             game.push(game_cubes);
         }
-        games.push(game);
-    }
-    games
+        Ok(game)
+    })
 }
 
-fn part1(input: &str) -> String {
-    let games = parse_input(input);
+fn part1(input: &str) -> Result<String, AocError> {
+    let games = parse_input(input)?;
     let bag = [12, 13, 14];
     let sum: u32 = games.iter()
         .enumerate()
@@ -38,11 +38,11 @@ fn part1(input: &str) -> String {
         })
         .sum();
 
-    sum.to_string()
+    Ok(sum.to_string())
 }
 
-fn part2(input: &str) -> String {
-    let games = parse_input(input);
+fn part2(input: &str) -> Result<String, AocError> {
+    let games = parse_input(input)?;
     let sum: u32 = games.iter()
         .map(|game| {
             let mut bag = [0, 0, 0];
@@ -55,11 +55,11 @@ fn part2(input: &str) -> String {
         })
         .sum();
 
-    sum.to_string()
+    Ok(sum.to_string())
 }
 
-crate::run!();
+crate::run!("Cube Conundrum", parse_input);
 
 crate::test_example_aoc!(8, 2286);
 
-crate::test_aoc!(1853, 72706);
+crate::test_aoc!();
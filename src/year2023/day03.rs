@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use crate::grid::Grid;
+
 struct NumberInfo {
     row: isize,
     cols: std::ops::Range<isize>,
@@ -7,41 +9,37 @@ struct NumberInfo {
 }
 
 struct Schematic {
-    grid: Vec<Vec<char>>,
+    grid: Grid<char>,
     number_infos: Vec<NumberInfo>,
 }
 
 impl Schematic {
     fn from_str(input: &str) -> Self {
-        let grid = input.lines()
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
+        let grid = Grid::parse(input, |c| c);
 
         // Find numbers
         let mut number_infos = Vec::new();
-        for row in 0..grid.len() {
+        for (row, line) in grid.rows().enumerate() {
             let mut start = None;
             let mut end = None;
             let mut number = 0;
-            for col in 0..grid[row].len() {
-                if grid[row][col].is_digit(10) {
+            for (col, ch) in line.iter().enumerate() {
+                if ch.is_ascii_digit() {
                     if start.is_none() {
                         start = Some(col);
                     }
                     end = Some(col);
-                    number = 10 * number + grid[row][col].to_digit(10).unwrap();
+                    number = 10 * number + ch.to_digit(10).unwrap();
                 }
-                else {
-                    if start.is_some() {
-                        number_infos.push(NumberInfo { row: row as isize, cols: start.unwrap() as isize .. end.unwrap() as isize + 1, value: number });
-                        start = None;
-                        end = None;
-                        number = 0;
-                    }
+                else if let (Some(start_col), Some(end_col)) = (start, end) {
+                    number_infos.push(NumberInfo { row: row as isize, cols: start_col as isize .. end_col as isize + 1, value: number });
+                    start = None;
+                    end = None;
+                    number = 0;
                 }
             }
-            if start.is_some() {
-                number_infos.push(NumberInfo { row: row as isize, cols: start.unwrap() as isize .. end.unwrap() as isize + 1, value: number });
+            if let (Some(start_col), Some(end_col)) = (start, end) {
+                number_infos.push(NumberInfo { row: row as isize, cols: start_col as isize .. end_col as isize + 1, value: number });
             }
         }
 
@@ -52,26 +50,20 @@ impl Schematic {
     }
 
     fn get(&self, row: isize, col: isize) -> char {
-        if row < 0 || col < 0 {
-            return '.';
-        }
-        match self.grid.get(row as usize) {
-            Some(line) => *line.get(col as usize).unwrap_or(&'.'),
-            None => '.'
-        }
+        self.grid.get((row, col)).copied().unwrap_or('.')
     }
 }
 
 fn part1(input: &str) -> String {
     let schematic = Schematic::from_str(input); 
 
-    let is_symbol = |ch: char| { !ch.is_digit(10) && ch != '.'};
+    let is_symbol = |ch: char| { !ch.is_ascii_digit() && ch != '.'};
 
     let mut sum = 0;
     for number in &schematic.number_infos {
         let mut valid = false;
         valid |= is_symbol(schematic.get(number.row - 1, number.cols.start - 1));
-        valid |= is_symbol(schematic.get(number.row + 0, number.cols.start - 1));
+        valid |= is_symbol(schematic.get(number.row, number.cols.start - 1));
         valid |= is_symbol(schematic.get(number.row + 1, number.cols.start - 1));
 
         for col in number.cols.clone() {
@@ -79,7 +71,7 @@ fn part1(input: &str) -> String {
             valid |= is_symbol(schematic.get(number.row + 1, col));
         }
         valid |= is_symbol(schematic.get(number.row - 1, number.cols.end));
-        valid |= is_symbol(schematic.get(number.row + 0, number.cols.end));
+        valid |= is_symbol(schematic.get(number.row, number.cols.end));
         valid |= is_symbol(schematic.get(number.row + 1, number.cols.end));
 
         if valid {
@@ -102,7 +94,7 @@ fn part2(input: &str) -> String {
 
     for number in &schematic.number_infos {
         check_gear(number.row - 1, number.cols.start - 1, number.value);
-        check_gear(number.row + 0, number.cols.start - 1, number.value);
+        check_gear(number.row, number.cols.start - 1, number.value);
         check_gear(number.row + 1, number.cols.start - 1, number.value);
 
         for col in number.cols.clone() {
@@ -111,19 +103,19 @@ fn part2(input: &str) -> String {
         }
 
         check_gear(number.row - 1, number.cols.end, number.value);
-        check_gear(number.row + 0, number.cols.end, number.value);
+        check_gear(number.row, number.cols.end, number.value);
         check_gear(number.row + 1, number.cols.end, number.value);
     }
     
-    let sum: u32 = gears.iter()
-        .map(|(_, numbers)| if numbers.len() == 2 { numbers[0] * numbers[1]} else { 0 })
+    let sum: u32 = gears.values()
+        .map(|numbers| if numbers.len() == 2 { numbers[0] * numbers[1]} else { 0 })
         .sum();
 
     sum.to_string()
 }
 
-crate::run!();
+crate::run!("Gear Ratios", Schematic::from_str);
 
 crate::test_example_aoc!(4361, 467835);
 
-crate::test_aoc!(556367, 89471771);
\ No newline at end of file
+crate::test_aoc!();
\ No newline at end of file
@@ -1,18 +1,21 @@
 use std::collections::{HashSet, HashMap};
 
-fn parse_input(input: &str) -> Vec<(HashSet<u32>, HashSet<u32>)> {
-    let mut cards = Vec::new();
-    for line in input.lines() {
-        let values = line.split(':').nth(1).unwrap();
-        let winning_numbers = values.split('|').next().unwrap().split_whitespace().map(|s| s.parse::<u32>().unwrap()).collect::<HashSet<_>>();
-        let numbers = values.split('|').nth(1).unwrap().split_whitespace().map(|s| s.parse::<u32>().unwrap()).collect::<HashSet<_>>();
-        cards.push((winning_numbers, numbers));
-    }
-    cards
+use crate::error::{parse_lines, AocError};
+
+type Card = (HashSet<u32>, HashSet<u32>);
+
+fn parse_input(input: &str) -> Result<Vec<Card>, AocError> {
+    parse_lines(input, |line| {
+        let invalid = || AocError::new(format!("invalid card '{}'", line));
+        let (_, values) = line.split_once(':').ok_or_else(invalid)?;
+        let (winning_numbers, numbers) = values.split_once('|').ok_or_else(invalid)?;
+        let parse = |numbers: &str| numbers.split_whitespace().map(|s| s.parse::<u32>().map_err(|_| invalid())).collect::<Result<HashSet<_>, _>>();
+        Ok((parse(winning_numbers)?, parse(numbers)?))
+    })
 }
 
-fn part1(input: &str) -> String {
-    let games = parse_input(input);
+fn part1(input: &str) -> Result<String, AocError> {
+    let games = parse_input(input)?;
     let sum = games.iter()
         .map(|(winning, number)| {
             let count  = number.intersection(winning).count();
@@ -24,11 +27,11 @@ fn part1(input: &str) -> String {
             }
         } )
         .sum::<u32>();
-    sum.to_string()
+    Ok(sum.to_string())
 }
 
-fn part2(input: &str) -> String {
-    let games = parse_input(input);
+fn part2(input: &str) -> Result<String, AocError> {
+    let games = parse_input(input)?;
     let mut cards: HashMap<usize, u32> = HashMap::new();
     for (game_id, (winning, numbers)) in games.iter().enumerate() {
         *cards.entry(game_id + 1).or_default() += 1;
@@ -38,12 +41,12 @@ fn part2(input: &str) -> String {
         }
     }
 
-    let sum = cards.iter().map(|(_, count)| count).sum::<u32>();
-    sum.to_string()
+    let sum = cards.values().sum::<u32>();
+    Ok(sum.to_string())
 }
 
-crate::run!();
+crate::run!("Scratchcards", parse_input);
 
 crate::test_example_aoc!(13, 30);
 
-crate::test_aoc!(27059, 5744979);
\ No newline at end of file
+crate::test_aoc!();
\ No newline at end of file
@@ -10,4 +10,4 @@ crate::run!();
 
 crate::test_example_aoc!(0000, 0000);
 
-crate::test_aoc!(0000, 0000);
\ No newline at end of file
+crate::test_aoc!();
\ No newline at end of file